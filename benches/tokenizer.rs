@@ -0,0 +1,114 @@
+// This file is part of "jup"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+//! Throughput of the tokenizer and the parser on a large generated Junon file
+//!
+//! Run with `cargo bench`
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use jup::lang::tokens::Token;
+use jup::parser::Parser;
+use jup::tokenizer::Tokenizer;
+
+/// Count of generated functions
+const FUNCTIONS: usize = 20_000;
+/// Each measure is the best of these runs
+const RUNS: usize = 5;
+
+fn main() {
+    let source_code = generate_source_code();
+    let size = source_code.len() as f64 / 1_000_000.0;
+
+    println!("Generated file : {:.2} MB, {} lines", size, source_code.lines().count());
+
+    let (tokens, errors) = Tokenizer::from_source_code(&source_code).tokenize();
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    let duration = measure(|| {
+        let mut tokenizer = Tokenizer::from_source_code(&source_code);
+        tokenizer.run();
+        black_box(tokenizer.tokenized().len());
+    });
+    report("Tokenizer::run()", duration, size, tokens.len());
+
+    let duration = measure(|| {
+        let tokenizer = Tokenizer::from_source_code(&source_code);
+        black_box(tokenizer.count());
+    });
+    report("Tokenizer iterator", duration, size, tokens.len());
+
+    let duration = measure(|| {
+        let mut tokenizer = Tokenizer::from_source_code(&source_code);
+        tokenizer.set_lossless(true);
+        tokenizer.run();
+        black_box(tokenizer.tokenized().len());
+    });
+    report("Tokenizer::run(), lossless", duration, size, tokens.len());
+
+    let duration = measure(|| {
+        let mut parser = Parser::new(tokens.clone());
+        parser.run();
+        black_box(parser.parsed().len());
+    });
+    report("Parser::run()", duration, size, tokens.len());
+
+    let mut tokenizer = Tokenizer::from_source_code(&source_code);
+    tokenizer.run();
+    let identifiers = tokens
+        .iter()
+        .filter(|token| matches!(token.token(), Token::Ident(_)))
+        .count();
+    println!("{} identifiers sharing {} names", identifiers, tokenizer.symbols().len());
+}
+
+/// Junon functions using variables, operations, arrays, conditions and 
+/// comments
+fn generate_source_code() -> String {
+    let mut source_code = String::from("//! Generated for the benchmark\n\n");
+
+    for i in 0..FUNCTIONS {
+        source_code += &format!(
+            "/// Function number {i}\n\
+            fun foo_{i}(a: int, b: byte): bigint[3] {{\n\
+            \tlet value_{n}: int = {{ a + 0x{i:X} * 2 }} // operation\n\
+            \tlet text: int = 'function {i}\\n'\n\
+            \tif value_{n} <= b {{\n\
+            \t\tvalue_{n} += {{ a << 1 }}\n\
+            \t}} else {{\n\
+            \t\t/* nothing */ value_{n} = 1_000\n\
+            \t}}\n\
+            \tret [value_{n}, 2.5e3, {i}bigint]\n\
+            }}\n\n",
+            i = i,
+            n = i % 100
+        );
+    }
+
+    source_code
+}
+
+fn measure(mut f: impl FnMut()) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn report(name: &str, duration: Duration, size: f64, tokens: usize) {
+    let seconds = duration.as_secs_f64();
+
+    println!(
+        "{:<28} {:>8.2} ms {:>8.2} MB/s {:>8.2} M tokens/s",
+        name,
+        seconds * 1000.0,
+        size / seconds,
+        tokens as f64 / seconds / 1_000_000.0
+    );
+}
@@ -0,0 +1,513 @@
+// This file is part of "jup"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+//! Checking of the inline assembly lines
+//!
+//! The lines are checked against the tables of this module and not against
+//! x64asm : it builds the instructions from its own types to write them as
+//! text, but cannot give back a mnemonic or a register from its name. Only the
+//! define directives used by jup are taken from x64asm, see
+//! `define_directive()`
+
+use x64asm::ddirective::DefineDirective::{self, *};
+
+use crate::diagnostic::Diagnostic;
+use crate::lang::elements::{
+    Element,
+    assembly::{Assembly, AssemblyLine},
+};
+use crate::lang::span::Span;
+
+/// Instructions accepted in inline assembly, with the minimum and the maximum
+/// count of operands
+///
+/// The conditional instructions are not listed, see `CONDITIONS`
+const MNEMONICS: &[(&str, usize, usize)] = &[
+    ("adc", 2, 2), ("add", 2, 2), ("and", 2, 2), ("bsf", 2, 2), ("bsr", 2, 2),
+    ("bswap", 1, 1), ("bt", 2, 2), ("btc", 2, 2), ("btr", 2, 2), ("bts", 2, 2),
+    ("call", 1, 1), ("cbw", 0, 0), ("cdq", 0, 0), ("cdqe", 0, 0), ("clc", 0, 0),
+    ("cld", 0, 0), ("cmc", 0, 0), ("cmp", 2, 2), ("cmpxchg", 2, 2),
+    ("cpuid", 0, 0), ("cqo", 0, 0), ("cwd", 0, 0), ("cwde", 0, 0),
+    ("dec", 1, 1), ("div", 1, 1), ("enter", 2, 2), ("hlt", 0, 0),
+    ("idiv", 1, 1), ("imul", 1, 3), ("inc", 1, 1), ("int", 1, 1),
+    ("int3", 0, 0), ("lea", 2, 2), ("leave", 0, 0), ("lfence", 0, 0),
+    ("lzcnt", 2, 2), ("mfence", 0, 0), ("mov", 2, 2), ("movabs", 2, 2),
+    ("movsx", 2, 2), ("movsxd", 2, 2), ("movzx", 2, 2), ("mul", 1, 1),
+    ("neg", 1, 1), ("nop", 0, 1), ("not", 1, 1), ("or", 2, 2), ("pause", 0, 0),
+    ("pop", 1, 1), ("popcnt", 2, 2), ("popf", 0, 0), ("popfq", 0, 0),
+    ("push", 1, 1), ("pushf", 0, 0), ("pushfq", 0, 0), ("rcl", 2, 2),
+    ("rcr", 2, 2), ("rdtsc", 0, 0), ("ret", 0, 1), ("rol", 2, 2), ("ror", 2, 2),
+    ("sal", 2, 2), ("sar", 2, 2), ("sbb", 2, 2), ("sfence", 0, 0),
+    ("shl", 2, 2), ("shld", 3, 3), ("shr", 2, 2), ("shrd", 3, 3), ("stc", 0, 0),
+    ("std", 0, 0), ("sub", 2, 2), ("syscall", 0, 0), ("test", 2, 2),
+    ("tzcnt", 2, 2), ("ud2", 0, 0), ("xadd", 2, 2), ("xchg", 2, 2),
+    ("xor", 2, 2),
+    // Jumps, their operand is a label
+    ("jecxz", 1, 1), ("jmp", 1, 1), ("jrcxz", 1, 1), ("loop", 1, 1),
+    ("loope", 1, 1), ("loopne", 1, 1),
+    // String instructions, used with a prefix
+    ("cmpsb", 0, 0), ("cmpsw", 0, 0), ("cmpsq", 0, 0), ("lodsb", 0, 0),
+    ("lodsw", 0, 0), ("lodsd", 0, 0), ("lodsq", 0, 0), ("movsb", 0, 0),
+    ("movsw", 0, 0), ("movsq", 0, 0), ("scasb", 0, 0), ("scasw", 0, 0),
+    ("scasd", 0, 0), ("scasq", 0, 0), ("stosb", 0, 0), ("stosw", 0, 0),
+    ("stosd", 0, 0), ("stosq", 0, 0),
+    // Also SSE instructions with operands
+    ("cmpsd", 0, 3), ("movsd", 0, 2),
+    // SSE
+    ("addpd", 2, 2), ("addps", 2, 2), ("addsd", 2, 2), ("addss", 2, 2),
+    ("andnpd", 2, 2), ("andnps", 2, 2), ("andpd", 2, 2), ("andps", 2, 2),
+    ("cmpss", 3, 3), ("comisd", 2, 2), ("comiss", 2, 2), ("cvtsd2si", 2, 2),
+    ("cvtsd2ss", 2, 2), ("cvtsi2sd", 2, 2), ("cvtsi2ss", 2, 2),
+    ("cvtss2sd", 2, 2), ("cvtss2si", 2, 2), ("cvttsd2si", 2, 2),
+    ("cvttss2si", 2, 2), ("divpd", 2, 2), ("divps", 2, 2), ("divsd", 2, 2),
+    ("divss", 2, 2), ("maxsd", 2, 2), ("maxss", 2, 2), ("minsd", 2, 2),
+    ("minss", 2, 2), ("movapd", 2, 2), ("movaps", 2, 2), ("movd", 2, 2),
+    ("movdqa", 2, 2), ("movdqu", 2, 2), ("movq", 2, 2), ("movss", 2, 2),
+    ("movupd", 2, 2), ("movups", 2, 2), ("mulpd", 2, 2), ("mulps", 2, 2),
+    ("mulsd", 2, 2), ("mulss", 2, 2), ("orpd", 2, 2), ("orps", 2, 2),
+    ("paddb", 2, 2), ("paddd", 2, 2), ("paddq", 2, 2), ("paddw", 2, 2),
+    ("pand", 2, 2), ("pandn", 2, 2), ("pcmpeqb", 2, 2), ("pcmpeqd", 2, 2),
+    ("por", 2, 2), ("pshufd", 3, 3), ("psubb", 2, 2), ("psubd", 2, 2),
+    ("psubq", 2, 2), ("psubw", 2, 2), ("pxor", 2, 2), ("shufps", 3, 3),
+    ("sqrtpd", 2, 2), ("sqrtps", 2, 2), ("sqrtsd", 2, 2), ("sqrtss", 2, 2),
+    ("subpd", 2, 2), ("subps", 2, 2), ("subsd", 2, 2), ("subss", 2, 2),
+    ("ucomisd", 2, 2), ("ucomiss", 2, 2), ("xorpd", 2, 2), ("xorps", 2, 2),
+    // Define directive not written by jup, see `define_directive()`
+    ("dw", 1, usize::MAX),
+];
+
+/// Condition codes of the conditional instructions, like "jne", "setne" and
+/// "cmovne"
+const CONDITIONS: &[&str] = &[
+    "a", "ae", "b", "be", "c", "e", "g", "ge", "l", "le", "na", "nae", "nb",
+    "nbe", "nc", "ne", "ng", "nge", "nl", "nle", "no", "np", "ns", "nz", "o",
+    "p", "pe", "po", "s", "z",
+];
+
+/// Written before a mnemonic, like "rep movsb"
+const PREFIXES: &[&str] = &["lock", "rep", "repe", "repne", "repnz", "repz"];
+
+/// Written before an operand, like "qword [rbp - 8]" or "push qword 1"
+const QUALIFIERS: &[&str] = &[
+    "byte", "word", "dword", "qword", "tword", "oword", "yword", "zword", "abs",
+    "far", "near", "rel", "short", "strict",
+];
+
+/// Registers except "r8" to "r15" and the SSE registers, see `is_register()`
+const REGISTERS: &[&str] = &[
+    "rax", "rbx", "rcx", "rdx", "rsi", "rdi", "rbp", "rsp",
+    "eax", "ebx", "ecx", "edx", "esi", "edi", "ebp", "esp",
+    "ax", "bx", "cx", "dx", "si", "di", "bp", "sp",
+    "al", "bl", "cl", "dl", "sil", "dil", "bpl", "spl",
+    "ah", "bh", "ch", "dh",
+];
+
+/// Checks the inline assembly lines of the parsed elements : unknown
+/// mnemonics, bad register names and wrong operands count are reported with
+/// their location
+pub struct AssemblyChecker<'a> {
+    parsed: &'a Vec<Element>,
+    errors: Vec<Diagnostic>,
+}
+
+impl<'a> AssemblyChecker<'a> {
+    pub fn new(parsed: &'a Vec<Element>) -> Self {
+        Self {
+            parsed,
+            errors: vec![],
+        }
+    }
+
+    pub fn run(&mut self) {
+        self.check_elements(self.parsed);
+    }
+
+    /// Returns the errors found, in the same order as in the source code
+    pub fn errors(&self) -> &Vec<Diagnostic> {
+        &self.errors
+    }
+
+    fn check_elements(&mut self, elements: &Vec<Element>) {
+        for element in elements {
+            self.check_element(element);
+        }
+    }
+
+    fn check_element(&mut self, element: &Element) {
+        match element {
+            Element::Assembly(assembly) => self.check_assembly(assembly),
+            Element::Call(call) => self.check_elements(call.args()),
+            Element::Condition(condition) => {
+                self.check_element(condition.condition());
+                self.check_element(condition.then());
+                if let Some(else_) = condition.else_() {
+                    self.check_element(else_);
+                }
+            }
+            Element::Function(function) => {
+                if let Some(body) = function.body() {
+                    self.check_element(body);
+                }
+            }
+            Element::Variable(variable) => {
+                if let Some(value) = variable.value() {
+                    self.check_element(value);
+                }
+            }
+            Element::Array(elements, _)
+                | Element::Expression(elements, _)
+                | Element::Parameters(elements, _) => self.check_elements(elements),
+            Element::Loop(element, _)
+                | Element::Return(Some(element), _) => self.check_element(element),
+            Element::UnaryOperation(operation) => self.check_element(operation.arg()),
+            Element::Operation(operation) => {
+                self.check_element(operation.arg1());
+                self.check_element(operation.arg2());
+            }
+            _ => {}
+        }
+    }
+
+    fn check_assembly(&mut self, assembly: &Assembly) {
+        for line in assembly.lines() {
+            self.check_line(line);
+        }
+
+        for binding in assembly.bindings() {
+            if !is_register(binding.register()) {
+                self.errors.push(Diagnostic::new(
+                    format!("Unknown register '{}'", binding.register()),
+                    binding.span()
+                ));
+            }
+        }
+    }
+
+    fn check_line(&mut self, line: &AssemblyLine) {
+        let code = line.code();
+        // Everything after ";" is a comment
+        let end = code.find(';').unwrap_or(code.len());
+
+        let mut words = Words::new(code, 0, end);
+
+        let mut mnemonic = match words.next() {
+            Some(word) => word,
+            None => return, // nothing to check
+        };
+
+        // A label can be written before the instruction
+        if code[mnemonic.1..].starts_with(':') {
+            words = Words::new(code, mnemonic.1 + 1, end);
+            mnemonic = match words.next() {
+                Some(word) => word,
+                None => return,
+            };
+        }
+        while PREFIXES.contains(&code[mnemonic.0..mnemonic.1].to_lowercase().as_str()) {
+            mnemonic = match words.next() {
+                Some(word) => word,
+                None => return,
+            };
+        }
+
+        let name = code[mnemonic.0..mnemonic.1].to_lowercase();
+        let (min, max) = match operands_count(&name) {
+            Some(count) => count,
+            None => {
+                self.errors.push(Diagnostic::new(
+                    format!("Unknown mnemonic '{}'", &code[mnemonic.0..mnemonic.1]),
+                    sub_span(line, mnemonic.0, mnemonic.1)
+                ));
+                return;
+            }
+        };
+
+        let operands = operands(code, mnemonic.1, end);
+
+        if operands.len() < min || operands.len() > max {
+            let expected = if min == max {
+                min.to_string()
+            } else if max == usize::MAX {
+                format!("at least {}", min)
+            } else {
+                format!("{} to {}", min, max)
+            };
+            self.errors.push(Diagnostic::new(
+                format!(
+                    "Wrong operands count for '{}', expected {} but found {}",
+                    name,
+                    expected,
+                    operands.len()
+                ),
+                line.span()
+            ));
+        }
+
+        // Data of define directives can be anything
+        if max == usize::MAX {
+            return;
+        }
+
+        for (start, end) in operands {
+            self.check_operand(line, start, end);
+        }
+    }
+
+    /// Checks the operand written at `start..end` in the line's code
+    ///
+    /// Labels and other symbols cannot be known here, only the names written
+    /// like a register are checked
+    fn check_operand(&mut self, line: &AssemblyLine, start: usize, end: usize) {
+        let operand = without_qualifiers(&line.code()[start..end]);
+        let span = sub_span(line, start, end);
+
+        let message = if operand.is_empty() {
+            "Missing operand".to_string()
+        } else if operand.contains('[') || operand.contains(']') {
+            // Memory operand, like "[rbp - 8]"
+            if operand.ends_with(']') && operand.contains('[') {
+                return;
+            }
+            format!("Invalid memory operand '{}'", operand)
+        } else if is_register(operand) || !is_register_like(operand) {
+            // Immediate value, label or symbol
+            return;
+        } else {
+            format!("Unknown register '{}'", operand)
+        };
+
+        self.errors.push(Diagnostic::new(message, span));
+    }
+}
+
+/// Minimum and maximum count of operands of the instruction, `None` when it
+/// does not exist
+fn operands_count(mnemonic: &str) -> Option<(usize, usize)> {
+    if define_directive(mnemonic).is_some() {
+        return Some((1, usize::MAX));
+    }
+    if let Some((_, min, max)) = MNEMONICS.iter().find(|(other, _, _)| *other == mnemonic) {
+        return Some((*min, *max));
+    }
+
+    let conditional = [("j", 1), ("set", 1), ("cmov", 2)]
+        .into_iter()
+        .find(|(prefix, _)| mnemonic
+            .strip_prefix(prefix)
+            .is_some_and(|condition| CONDITIONS.contains(&condition))
+        );
+
+    conditional.map(|(_, count)| (count, count))
+}
+
+/// Define directive of x64asm written with this name, its operands are not
+/// checked
+fn define_directive(mnemonic: &str) -> Option<DefineDirective> {
+    match mnemonic {
+        "db" => Some(Db),
+        "dd" => Some(Dd),
+        "dq" => Some(Dq),
+        _ => None,
+    }
+}
+
+/// Operand without the size and the other qualifiers written before it
+fn without_qualifiers(operand: &str) -> &str {
+    let mut operand = operand;
+
+    while let Some((word, rest)) = operand.split_once(char::is_whitespace) {
+        if !QUALIFIERS.contains(&word.to_lowercase().as_str()) {
+            break;
+        }
+        operand = rest.trim_start();
+    }
+
+    match QUALIFIERS.contains(&operand.to_lowercase().as_str()) {
+        true => "",
+        false => operand,
+    }
+}
+
+/// Whether the name is a general purpose register, like "rax" or "r8d", or an
+/// SSE register like "xmm0"
+fn is_register(name: &str) -> bool {
+    let name = name.to_lowercase();
+
+    if REGISTERS.contains(&name.as_str()) {
+        return true;
+    }
+
+    match numbered_register(&name) {
+        // "r8" to "r15" with an optional size suffix
+        Some(("r", number, suffix)) => suffix.len() <= 1 && (8..=15).contains(&number),
+        Some((_, number, suffix)) => suffix.is_empty() && number <= 15,
+        None => false,
+    }
+}
+
+/// Whether the name is written like a numbered register, a wrong one like 
+/// "r16" is not a symbol
+fn is_register_like(name: &str) -> bool {
+    numbered_register(&name.to_lowercase()).is_some()
+}
+
+/// Prefix, number and size suffix of a register like "r8d" or "xmm0"
+fn numbered_register(name: &str) -> Option<(&str, u8, &str)> {
+    let prefix = ["xmm", "ymm", "r"]
+        .into_iter()
+        .find(|prefix| name.starts_with(prefix))?;
+
+    let rest = &name[prefix.len()..];
+    let suffix = rest.trim_start_matches(|c: char| c.is_ascii_digit());
+    let number = rest[..rest.len() - suffix.len()].parse::<u8>().ok()?;
+
+    if suffix.chars().all(|c| "dwb".contains(c)) {
+        Some((prefix, number, suffix))
+    } else {
+        None
+    }
+}
+
+/// Byte ranges of the operands written in `code[start..end]`, separated by
+/// commas and without their surrounding whitespaces
+fn operands(code: &str, start: usize, end: usize) -> Vec<(usize, usize)> {
+    if code[start..end].trim().is_empty() {
+        return vec![];
+    }
+
+    let mut operands = vec![];
+    let mut operand_start = start;
+
+    for (i, c) in code[start..end].char_indices() {
+        if c == ',' {
+            operands.push(trimmed(code, operand_start, start + i));
+            operand_start = start + i + 1;
+        }
+    }
+    operands.push(trimmed(code, operand_start, end));
+
+    operands
+}
+
+/// Byte range of `code[start..end]` without its surrounding whitespaces
+fn trimmed(code: &str, start: usize, end: usize) -> (usize, usize) {
+    let text = &code[start..end];
+    let start = start + (text.len() - text.trim_start().len());
+    let end = end - (text.len() - text.trim_end().len());
+    (start, end.max(start))
+}
+
+/// Location of `line.code()[start..end]` in the source code
+fn sub_span(line: &AssemblyLine, start: usize, end: usize) -> Span {
+    let span = line.span();
+    let column = span.column() + line.code()[..start].chars().count();
+
+    Span::new(span.start() + start, span.start() + end, span.line(), column)
+}
+
+/// Iterator over the byte ranges of the words written in `code[start..end]`,
+/// a word ends before a whitespace or ":"
+struct Words<'a> {
+    code: &'a str,
+    pos: usize,
+    end: usize,
+}
+
+impl<'a> Words<'a> {
+    fn new(code: &'a str, start: usize, end: usize) -> Self {
+        Self { code, pos: start, end }
+    }
+}
+
+impl<'a> Iterator for Words<'a> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.code[self.pos..self.end];
+        let start = self.pos + (rest.len() - rest.trim_start().len());
+
+        let len = self.code[start..self.end]
+            .find(|c: char| c.is_whitespace() || c == ':')
+            .unwrap_or(self.end - start);
+
+        if len == 0 {
+            return None;
+        }
+        self.pos = start + len;
+        Some((start, start + len))
+    }
+}
+
+#[test]
+fn check_assembly() {
+    use crate::parser::Parser;
+    use crate::tokenizer::Tokenizer;
+
+    let source_code = "fun foo {\n".to_owned()
+        + "    @{\n"
+        + "        start: mov rax, rdi ; comment\n"
+        + "        mvo rax, 1\n"
+        + "        add rax\n"
+        + "        mov r16, [rbp - 8]\n"
+        + "        jne start\n"
+        + "        rep stosb\n"
+        + "        ret\n"
+        + "    } in(rdi) a, out(rxa) b\n"
+        + "    @ push R15\n"
+        + "    loop {\n"
+        + "        @ push R16\n"
+        + "    }\n"
+        + "    @{\n"
+        + "        cmove rax, rbx\n"
+        + "        setne al\n"
+        + "        movabs rax, 1\n"
+        + "        cpuid\n"
+        + "        bswap eax\n"
+        + "        addsd xmm0, xmm15\n"
+        + "        mov rsi, msg\n"
+        + "        push qword 1\n"
+        + "        mov qword [rsp], xmm16\n"
+        + "        setne\n"
+        + "        push qword\n"
+        + "        msg: db 10, [rax]\n"
+        + "        dq\n"
+        + "    }\n"
+        + "}\n";
+
+    let mut tokenizer = Tokenizer::from_source_code(&source_code);
+    tokenizer.run();
+
+    let mut parser = Parser::new(tokenizer.tokenized().clone());
+    parser.run();
+
+    let mut checker = AssemblyChecker::new(parser.parsed());
+    checker.run();
+
+    let errors: Vec<(String, &str)> = checker.errors()
+        .iter()
+        .map(|error| (error.to_string(), &source_code[error.span().range()]))
+        .collect();
+
+    assert_eq!(errors, vec![
+        ("4:9: Unknown mnemonic 'mvo'".to_string(), "mvo"),
+        ("5:9: Wrong operands count for 'add', expected 2 but found 1".to_string(), "add rax"),
+        ("6:13: Unknown register 'r16'".to_string(), "r16"),
+        ("10:18: Unknown register 'rxa'".to_string(), "out(rxa) b"),
+        ("13:16: Unknown register 'R16'".to_string(), "R16"),
+        ("24:26: Unknown register 'xmm16'".to_string(), "xmm16"),
+        ("25:9: Wrong operands count for 'setne', expected 1 but found 0".to_string(), "setne"),
+        ("26:14: Missing operand".to_string(), "qword"),
+        ("28:9: Wrong operands count for 'dq', expected at least 1 but found 0".to_string(), "dq"),
+    ]);
+}
+
+#[test]
+fn registers() {
+    for register in ["rax", "EAX", "r8", "r15d", "r10w", "r9b", "sil", "ah", "xmm0", "xmm15"] {
+        assert!(is_register(register), "{}", register);
+    }
+    for register in ["r7", "r16", "r8q", "r8dd", "rxa", "r", "", "xmm16", "xmm1d"] {
+        assert!(!is_register(register), "{}", register);
+    }
+}
@@ -2,4 +2,5 @@
 // Under the MIT License
 // Copyright (c) Junon, Antonin Hérault
 
+pub mod assembly;
 pub mod syntax;
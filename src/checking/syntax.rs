@@ -4,69 +4,51 @@
 
 use rslog::{level::LogLevel, log::Log, logger::Logger, *};
 
-use crate::lang::tokens::Token;
+use crate::lang::tokens::{SpannedToken, Token};
 
 pub struct SyntaxChecker<'a> {
     source: &'a String,
-    parsed: &'a Vec<Token>,
+    parsed: &'a Vec<SpannedToken>,
     logger: Logger,
 
-    previous_token: &'a Token,
-    current_token: &'a Token,
+    /// `None` before the first token of the parsed content
+    previous_token: Option<&'a SpannedToken>,
+    current_token: Option<&'a SpannedToken>,
 
-    /// The current line index
-    line_i: usize,
-    // The current toke index (in all file, != than `token_i_on_line`)
+    // The current toke index (in all file)
     token_i: usize,
-    /// The current token index on the current line (`line_i`)
-    token_i_on_line: usize,
 
     /// "Should the line be read more ?"
     /// It stops analyzing the tokens on the current line
     break_line: bool,
-
-    /// Positions of `NewLine` tokens
-    new_lines: Vec<usize>,
 }
 
 impl<'a> SyntaxChecker<'a> {
-    pub fn new(source: &'a String, parsed: &'a Vec<Token>) -> Self {
-        // Retrieves all `NewLine` tokens positions
-        let mut new_lines: Vec<usize> = vec![];
-        for (i, token) in parsed.iter().enumerate() {
-            if *token == Token::NewLine {
-                new_lines.push(i + 1);
-            }
-        }
-
+    pub fn new(source: &'a String, parsed: &'a Vec<SpannedToken>) -> Self {
         Self {
             source,
             parsed,
             logger: Logger::new(),
 
-            previous_token: &Token::None,
-            current_token: &Token::None,
+            previous_token: None,
+            current_token: None,
 
-            line_i: 0,
             token_i: 0,
-            token_i_on_line: 0,
 
             break_line: false,
-
-            new_lines,
         }
     }
 
     pub fn run(&mut self) {
         for token in self.parsed {
             self.previous_token = self.current_token;
-            self.current_token = token;
+            self.current_token = Some(token);
 
             // Skip token check until `Token::NewLine` found
             if self.break_line {
                 self.increment();
 
-                if *self.previous_token != Token::NewLine {
+                if self.previous_token.map(|t| t.token()) != Some(&Token::NewLine) {
                     continue;
                 }
 
@@ -88,22 +70,34 @@ impl<'a> SyntaxChecker<'a> {
     /// We moved to the next token
     pub fn increment(&mut self) {
         self.token_i += 1;
-        self.token_i_on_line += 1;
     }
 
     /// Checks the previous token when it's not already skipped with
     /// `break_line`
     pub fn check_token(&mut self) {
-        let cause = source_to_string(self.source.to_string(), self.line_i, self.token_i);
+        // First token of the parsed content
+        let previous_token = match self.previous_token {
+            Some(previous_token) => previous_token,
+            None => return,
+        };
 
-        match *self.previous_token {
+        let cause = source_to_string(
+            self.source.to_string(), 
+            previous_token.span().line() - 1, 
+            self.token_i
+        );
+
+        match *previous_token.token() {
             Token::Assembly => {
                 self.break_line = true;
             }
+            // Lines of an assembly block
+            Token::AssemblyCode(_) => {}
             Token::Assign => {}
             Token::BracketOpen => {}
             Token::BracketClose => {}
             Token::Comma => {}
+            Token::DocComment(_) | Token::ModuleDocComment(_) => {}
             Token::Function => {
                 self.break_line = true;
             }
@@ -118,13 +112,27 @@ impl<'a> SyntaxChecker<'a> {
             Token::Variable | Token::Static => {
                 self.break_line = true;
             }
-            Token::NewLine => {
-                self.token_i_on_line = 0;
-                self.line_i += 1;
-            }
-            // First token of the parsed content
+            // Can be an assignment or a function call
+            Token::Ident(_) => {}
+            Token::NewLine => {}
             Token::None => (),
 
+            ref token if token.is_literal() => {
+                self.logger.add_log(
+                    Log::new(
+                        LogLevel::Error,
+                        "Unused value".to_string(),
+                        format!(
+                            "{}A value cannot be used as an instruction, found '{}'",
+                            &self.fmt_generate_line(previous_token),
+                            token.to_string()
+                        ),
+                    )
+                    .add_cause(&cause)
+                    .finish(),
+                );
+            }
+
             _ => {
                 self.logger.add_log(
                     Log::new(
@@ -132,8 +140,8 @@ impl<'a> SyntaxChecker<'a> {
                         "Invalid token".to_string(),
                         format!(
                             "{}No valid instruction found for token '{}'",
-                            &self.fmt_generate_line(),
-                            self.previous_token.to_string()
+                            &self.fmt_generate_line(previous_token),
+                            previous_token.token().to_string()
                         ),
                     )
                     .add_cause(&cause)
@@ -143,21 +151,27 @@ impl<'a> SyntaxChecker<'a> {
         }
     }
 
-    fn generate_line(&self) -> Vec<Token> {
-        let i = self.new_lines[self.line_i - 1];
+    /// Retrieves all the tokens written on the line, thanks to their location
+    fn generate_line(&self, line: usize) -> Vec<Token> {
+        self.parsed
+            .iter()
+            .filter(|token| token.span().line() == line && **token != Token::NewLine)
+            .map(|token| token.token().clone())
+            .collect()
+    }
 
-        let j = if i == self.new_lines[self.new_lines.len() - 1] {
-            self.new_lines.len() - 1
-        } else if self.line_i != self.new_lines.len() {
-            self.new_lines[self.line_i]
-        } else {
-            self.new_lines.len() - 1
-        };
+    fn fmt_generate_line(&self, token: &SpannedToken) -> String {
+        let span = token.span();
 
-        self.parsed[i..j - 1].to_vec()
-    }
+        // Index of the token on its line, starting from `1`
+        let token_i_on_line = 1 + self.parsed
+            .iter()
+            .filter(|other| {
+                other.span().line() == span.line() 
+                    && other.span().start() < span.start()
+            })
+            .count();
 
-    fn fmt_generate_line(&self) -> String {
-        line_to_string(&self.generate_line(), self.token_i_on_line)
+        line_to_string(&self.generate_line(span.line()), token_i_on_line)
     }
 }
@@ -0,0 +1,200 @@
+// This file is part of "jup"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use unicode_xid::UnicodeXID;
+
+use crate::lang::tokens::{Token, OPERATORS};
+
+/// Indentation written for each level of `Token::BracketOpen`
+const INDENT: &str = "    ";
+
+/// Turns tokens back into source code, for code generators building tokens
+///
+/// The source code is written in a uniform style : four spaces indentation,
+/// spaces around the operators and none inside the parentheses. The original
+/// layout is not kept, see the lossless mode of `Tokenizer` for it
+///
+/// Tokenizing the returned source code gives back the same tokens
+pub fn detokenize<'a>(tokens: impl IntoIterator<Item = &'a Token>) -> String {
+    let mut source_code = String::new();
+
+    let mut depth: usize = 0;
+    // `None` at the beginning of a line
+    let mut previous: Option<&Token> = None;
+    // The previous token is an unary operator, like "-" in "-5"
+    let mut is_unary = false;
+
+    for token in tokens {
+        match *token {
+            Token::None => continue,
+            Token::NewLine => {
+                source_code.push('\n');
+                previous = None;
+                continue;
+            }
+            Token::BracketClose => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+
+        match previous {
+            None => source_code.push_str(&INDENT.repeat(depth)),
+            Some(previous) if would_merge(previous, token) => source_code.push(' '),
+            Some(_) if is_unary => {}
+            Some(previous) if needs_space(previous, token) => source_code.push(' '),
+            Some(_) => {}
+        }
+        source_code.push_str(&token.to_string());
+
+        if *token == Token::BracketOpen {
+            depth += 1;
+        }
+
+        is_unary = match *token {
+            Token::Not => true,
+            Token::Minus => !previous.is_some_and(is_value_end),
+            _ => false,
+        };
+        previous = Some(token);
+    }
+
+    source_code
+}
+
+/// Whether a space is written between the two tokens, for readability
+fn needs_space(previous: &Token, token: &Token) -> bool {
+    match (previous, token) {
+        (
+            _,
+            Token::Comma
+                | Token::SemiColon
+                | Token::TypeDef
+                | Token::ParenClose
+                | Token::SquareBracketClose
+                | Token::Point
+                | Token::DoubleColon
+        ) => false,
+        (
+            Token::ParenOpen
+                | Token::SquareBracketOpen
+                | Token::Point
+                | Token::DoubleColon,
+            _
+        ) => false,
+        // Function calls, parameters and array types
+        (Token::Ident(_), Token::ParenOpen | Token::SquareBracketOpen) => false,
+        // Assembly block
+        (Token::Assembly, Token::BracketOpen) => false,
+        _ => true,
+    }
+}
+
+/// Whether the two tokens written without space would be read as other tokens
+fn would_merge(previous: &Token, token: &Token) -> bool {
+    let previous = previous.to_string();
+    let token = token.to_string();
+
+    let joined = previous.clone() + &token;
+
+    // Words like identifiers, keywords and numbers
+    let is_word_end = previous.ends_with(|c: char| c.is_xid_continue());
+    let is_word_start = token.starts_with(|c: char| c.is_xid_continue());
+
+    // The tokenizer takes the longest operator
+    let is_longer_operator = OPERATORS
+        .iter()
+        .any(|operator| operator.len() > previous.len() && joined.starts_with(operator));
+
+    // A number followed by a point would be a floating-point number
+    let is_float = previous.starts_with(|c: char| c.is_ascii_digit()) && token == ".";
+
+    (is_word_end && is_word_start) || is_longer_operator || is_float || joined.contains("/*")
+}
+
+/// Whether the token can be the end of a value, so a following "-" is a
+/// binary operator
+fn is_value_end(token: &Token) -> bool {
+    matches!(
+        *token,
+        Token::Ident(_)
+            | Token::ParenClose
+            | Token::SquareBracketClose
+            | Token::BracketClose
+    ) || token.is_literal()
+}
+
+#[test]
+fn detokenize_tokens() {
+    use crate::tokenizer::Tokenizer;
+
+    let source_code = "/// Doc\nfun foo( a :int,b:byte ):int[ 2 ]{\n"
+        .to_owned()
+        + "let b:int[2]=[ 1,-2 ]\n"
+        + "\n"
+        + "  if b==a {\n"
+        + "ret {a*-(2+x.y)}\n"
+        + "} else {\n"
+        + "@{\n"
+        + "  nop\n"
+        + "}\n"
+        + "@ syscall\n"
+        + "foo('it\\'s',!a)\n"
+        + "}\n"
+        + "}";
+
+    let mut tokenizer = Tokenizer::from_source_code(&source_code);
+    tokenizer.run();
+
+    let tokens: Vec<Token> = tokenizer.tokenized()
+        .iter()
+        .map(|token| token.token().clone())
+        .collect();
+
+    assert_eq!(detokenize(&tokens), "/// Doc\n".to_owned()
+        + "fun foo(a: int, b: byte): int[2] {\n"
+        + "    let b: int[2] = [1, -2]\n"
+        + "\n"
+        + "    if b == a {\n"
+        + "        ret { a * -(2 + x.y) }\n"
+        + "    } else {\n"
+        + "        @{\n"
+        + "            nop\n"
+        + "        }\n"
+        + "        @ syscall\n"
+        + "        foo('it\\'s', !a)\n"
+        + "    }\n"
+        + "}\n"
+    );
+}
+
+#[test]
+fn same_tokens() {
+    let sources = [
+        "a < = b ! = c - - d / / e / * f 1 . 5 let a let".to_string(),
+        "a: :b : : c -> d :: e.f 0x10 . g".to_string(),
+        "x = 2.5e3 + 1bigint << 3 >> 'a' != '\\u{e9}'".to_string(),
+        std::fs::read_to_string("tests/test1.ju").unwrap(),
+        std::fs::read_to_string("tests/parser.ju").unwrap(),
+        std::fs::read_to_string("tests/operations.ju").unwrap(),
+    ];
+
+    for source_code in sources {
+        let tokens = tokens_of(&source_code);
+        assert_eq!(tokens_of(&detokenize(&tokens)), tokens, "{}", source_code);
+    }
+}
+
+/// Tokens of the source code, without the new lines
+#[cfg(test)]
+fn tokens_of(source_code: &str) -> Vec<Token> {
+    use crate::tokenizer::Tokenizer;
+
+    let mut tokenizer = Tokenizer::from_source_code(source_code);
+    tokenizer.run();
+
+    tokenizer.tokenized()
+        .iter()
+        .map(|token| token.token().clone())
+        .filter(|token| *token != Token::NewLine)
+        .collect()
+}
@@ -0,0 +1,35 @@
+// This file is part of "jup"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use std::fmt;
+
+use crate::lang::span::Span;
+
+/// An error found in the source code, with the location where it was found
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Diagnostic {
+    message: String,
+    span: Span,
+}
+
+impl Diagnostic {
+    pub fn new(message: String, span: Span) -> Self {
+        Self { message, span }
+    }
+
+    pub fn message(&self) -> &String {
+        &self.message
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    /// Formatted as "line:column: message"
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.span.line(), self.span.column(), self.message)
+    }
+}
@@ -0,0 +1,252 @@
+// This file is part of "jup"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+//! Typed syntax tree of a source file : a module of items, the functions'
+//! statements and their expressions
+//!
+//! Given by `Parser::parse_module()`, built from the parsed elements
+
+use crate::lang::elements::{
+    assembly::Assembly,
+    function::Function,
+    variable::Variable,
+};
+use crate::lang::span::Span;
+use crate::lang::tokens::Token;
+
+/// Whole source file
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Module {
+    /// Lines of the "//!" comments
+    docs: Vec<String>,
+    items: Vec<Item>,
+}
+
+impl Module {
+    pub fn new(docs: Vec<String>, items: Vec<Item>) -> Self {
+        Self {
+            docs,
+            items,
+        }
+    }
+
+    pub fn docs(&self) -> &Vec<String> {
+        &self.docs
+    }
+
+    pub fn items(&self) -> &Vec<Item> {
+        &self.items
+    }
+}
+
+/// Declaration written at the module's level
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Item {
+    Function(FunctionDef),
+    /// Always a static variable
+    Static(Let),
+    /// Statement written outside of any function, like in a script
+    Statement(Statement),
+}
+
+impl Item {
+    pub fn span(&self) -> Span {
+        match *self {
+            Self::Function(ref function) => function.span(),
+            Self::Static(ref static_) => static_.span(),
+            Self::Statement(ref statement) => statement.span(),
+        }
+    }
+}
+
+/// Function with its body, the body is `None` for a declaration only
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FunctionDef {
+    /// Name, parameters, return type and documentation, without the body
+    signature: Function,
+    body: Option<Block>,
+}
+
+impl FunctionDef {
+    pub fn new(signature: Function, body: Option<Block>) -> Self {
+        Self {
+            signature,
+            body,
+        }
+    }
+
+    pub fn signature(&self) -> &Function {
+        &self.signature
+    }
+
+    pub fn body(&self) -> Option<&Block> {
+        self.body.as_ref()
+    }
+
+    /// Covers the signature and the body
+    pub fn span(&self) -> Span {
+        match self.body {
+            Some(ref body) => self.signature.span().to(&body.span()),
+            None => self.signature.span(),
+        }
+    }
+}
+
+/// Variable declaration with its value
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Let {
+    /// Name, type and documentation
+    variable: Variable,
+    value: Option<Expression>,
+}
+
+impl Let {
+    pub fn new(variable: Variable, value: Option<Expression>) -> Self {
+        Self {
+            variable,
+            value,
+        }
+    }
+
+    pub fn variable(&self) -> &Variable {
+        &self.variable
+    }
+
+    pub fn value(&self) -> Option<&Expression> {
+        self.value.as_ref()
+    }
+
+    /// Covers the declaration and the value
+    pub fn span(&self) -> Span {
+        match self.value {
+            Some(ref value) => self.variable.span().to(&value.span()),
+            None => self.variable.span(),
+        }
+    }
+}
+
+/// Statements written into "{ ... }"
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Block {
+    statements: Vec<Statement>,
+    span: Span,
+}
+
+impl Block {
+    pub fn new(statements: Vec<Statement>, span: Span) -> Self {
+        Self {
+            statements,
+            span,
+        }
+    }
+
+    pub fn statements(&self) -> &Vec<Statement> {
+        &self.statements
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Statement {
+    Let(Let),
+    /// "ret" with its value
+    Return(Option<Expression>, Span),
+    If(If),
+    Loop(Block, Span),
+    Break(Span),
+    Continue(Span),
+    Assembly(Assembly),
+    /// Expression used as a statement, like a call or an assignment
+    Expression(Expression),
+    /// Malformed source code, the error is given with the module
+    Error(Span),
+}
+
+impl Statement {
+    pub fn span(&self) -> Span {
+        match *self {
+            Self::Let(ref let_) => let_.span(),
+            Self::If(ref if_) => if_.span(),
+            Self::Assembly(ref assembly) => assembly.span(),
+            Self::Expression(ref expression) => expression.span(),
+            Self::Return(_, span)
+                | Self::Loop(_, span)
+                | Self::Break(span)
+                | Self::Continue(span)
+                | Self::Error(span) => span,
+        }
+    }
+}
+
+/// Condition with the block to run when it's true, and the one to run
+/// otherwise
+///
+/// "else if" is an else block with only one `Statement::If`
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct If {
+    condition: Expression,
+    then: Block,
+    else_: Option<Block>,
+    span: Span,
+}
+
+impl If {
+    pub fn new(condition: Expression, then: Block, else_: Option<Block>, span: Span) -> Self {
+        Self {
+            condition,
+            then,
+            else_,
+            span,
+        }
+    }
+
+    pub fn condition(&self) -> &Expression {
+        &self.condition
+    }
+
+    pub fn then(&self) -> &Block {
+        &self.then
+    }
+
+    pub fn else_(&self) -> Option<&Block> {
+        self.else_.as_ref()
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Expression {
+    /// Literal or identifier
+    Value(Token, Span),
+    /// Binary operation with its operator, like "a + 1" or "a = 1"
+    Operation(Token, Box<Expression>, Box<Expression>, Span),
+    /// Prefix operation with its operator, like "-a" or "!a"
+    UnaryOperation(Token, Box<Expression>, Span),
+    /// Called function's name with the arguments
+    Call(String, Vec<Expression>, Span),
+    Array(Vec<Expression>, Span),
+    Block(Block),
+    /// Malformed or missing expression, the error is given with the module
+    Error(Span),
+}
+
+impl Expression {
+    pub fn span(&self) -> Span {
+        match *self {
+            Self::Block(ref block) => block.span(),
+            Self::Value(_, span)
+                | Self::Operation(_, _, _, span)
+                | Self::UnaryOperation(_, _, span)
+                | Self::Call(_, _, span)
+                | Self::Array(_, span)
+                | Self::Error(span) => span,
+        }
+    }
+}
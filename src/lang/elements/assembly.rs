@@ -0,0 +1,129 @@
+// This file is part of "jup"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use crate::lang::span::Span;
+use crate::lang::tokens::Token;
+
+/// Inline assembly code, written on one line after "@" or on several lines
+/// into an assembly block "@{ ... }"
+///
+/// Example :
+/// ```text
+/// @{
+///     mov rax, rdi
+///     add rax, 1
+/// } in(rdi) a, out(rax) b
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Assembly {
+    lines: Vec<AssemblyLine>,
+    /// Junon variables given to or retrieved from the registers
+    bindings: Vec<Binding>,
+    span: Span,
+}
+
+impl Assembly {
+    pub fn new(lines: Vec<AssemblyLine>, bindings: Vec<Binding>, span: Span) -> Self {
+        Self {
+            lines,
+            bindings,
+            span,
+        }
+    }
+
+    pub fn lines(&self) -> &Vec<AssemblyLine> {
+        &self.lines
+    }
+
+    pub fn bindings(&self) -> &Vec<Binding> {
+        &self.bindings
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// One line of raw assembly code, without its indentation
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AssemblyLine {
+    code: String,
+    span: Span,
+}
+
+impl AssemblyLine {
+    pub fn new(code: String, span: Span) -> Self {
+        Self {
+            code,
+            span,
+        }
+    }
+
+    pub fn code(&self) -> &String {
+        &self.code
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// Direction of the value between the variable and the register
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum BindingKind {
+    /// "in" : the variable's value is put in the register before the code
+    In,
+    /// "out" : the register's value is put in the variable after the code
+    Out,
+    /// "inout" : both of them
+    InOut,
+}
+
+impl BindingKind {
+    /// Returns `None` when the string is not a binding kind
+    pub fn from_string(string: &str) -> Option<Self> {
+        match string {
+            "in" => Some(Self::In),
+            "out" => Some(Self::Out),
+            "inout" => Some(Self::InOut),
+            _ => None,
+        }
+    }
+}
+
+/// Binding between a Junon variable and a register, like "in(rdi) a"
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Binding {
+    kind: BindingKind,
+    register: String,
+    variable: Token,
+    span: Span,
+}
+
+impl Binding {
+    pub fn new(kind: BindingKind, register: String, variable: Token, span: Span) -> Self {
+        Self {
+            kind,
+            register,
+            variable,
+            span,
+        }
+    }
+
+    pub fn kind(&self) -> BindingKind {
+        self.kind
+    }
+
+    pub fn register(&self) -> &String {
+        &self.register
+    }
+
+    pub fn variable(&self) -> String {
+        self.variable.to_string()
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
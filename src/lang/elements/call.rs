@@ -0,0 +1,43 @@
+// This file is part of "jup"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use crate::lang::span::Span;
+use crate::lang::tokens::Token;
+use super::Element;
+
+/// Call of a function with its arguments, like "foo(a, { 5 + 2 })"
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Call {
+    id: Token,
+    /// Parsed arguments, without the commas
+    args: Vec<Element>,
+    span: Span,
+}
+
+impl Call {
+    pub fn new(id: Token, args: Vec<Element>, span: Span) -> Self {
+        Self {
+            id,
+            args,
+            span,
+        }
+    }
+
+    pub fn id(&self) -> String {
+        self.id.to_string()
+    }
+
+    pub fn args(&self) -> &Vec<Element> {
+        &self.args
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Name, arguments and location, moved out of the object
+    pub fn into_parts(self) -> (Token, Vec<Element>, Span) {
+        (self.id, self.args, self.span)
+    }
+}
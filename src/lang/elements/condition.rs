@@ -0,0 +1,50 @@
+// This file is part of "jup"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use crate::lang::span::Span;
+use super::Element;
+
+/// "if" with its condition, its block and the else block, like
+/// "if a == 1 { ... } else { ... }"
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Condition {
+    condition: Box<Element>,
+    /// Always `Element::Expression`
+    then: Box<Element>,
+    /// `Element::Expression`, or `Element::Condition` for "else if"
+    else_: Option<Box<Element>>,
+    span: Span,
+}
+
+impl Condition {
+    pub fn new(condition: Element, then: Element, else_: Option<Element>, span: Span) -> Self {
+        Self {
+            condition: Box::new(condition),
+            then: Box::new(then),
+            else_: else_.map(Box::new),
+            span,
+        }
+    }
+
+    pub fn condition(&self) -> &Element {
+        &self.condition
+    }
+
+    pub fn then(&self) -> &Element {
+        &self.then
+    }
+
+    pub fn else_(&self) -> Option<&Element> {
+        self.else_.as_deref()
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Condition, block, else block and location, moved out of the object
+    pub fn into_parts(self) -> (Element, Element, Option<Element>, Span) {
+        (*self.condition, *self.then, self.else_.map(|else_| *else_), self.span)
+    }
+}
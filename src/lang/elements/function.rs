@@ -2,6 +2,7 @@
 // Under the MIT License
 // Copyright (c) Junon, Antonin Hérault
 
+use crate::lang::span::Span;
 use crate::lang::tokens::Token;
 use super::{
     Element,
@@ -11,29 +12,95 @@ use super::{
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Function {
     id: Token,
-    /// This element has to be `Element::Parameters`
-    params: Box<Element>,
+    params: Vec<Parameter>,
     return_type: Type,
+    /// This element has to be `Element::Expression`, `None` for a 
+    /// declaration only
+    body: Option<Box<Element>>,
+    /// Covers the body
+    span: Span,
+    /// Lines of the "///" comments written before the function
+    docs: Vec<String>,
 }
 
 impl Function {
-    pub fn new(id: Token, params: Element, return_type: Type) -> Self {
+    pub fn new(id: Token, params: Vec<Parameter>, return_type: Type, span: Span) -> Self {
         Function {
             id,
-            params: Box::new(params),
+            params,
             return_type,
+            body: None,
+            span,
+            docs: vec![],
         }
     }
 
+    /// The function's location is extended to the body
+    pub fn set_body(&mut self, body: Element) {
+        self.span = self.span.to(&body.span());
+        self.body = Some(Box::new(body));
+    }
+
+    /// Takes the body out of the function, its location still covers the body
+    pub fn take_body(&mut self) -> Option<Element> {
+        self.body.take().map(|body| *body)
+    }
+
+    pub fn set_docs(&mut self, docs: Vec<String>) {
+        self.docs = docs;
+    }
+
     pub fn id(&self) -> String {
         self.id.to_string()
     }
 
-    pub fn params(&self) -> Element {
-        *self.params.clone()
+    pub fn params(&self) -> &Vec<Parameter> {
+        &self.params
     }
 
     pub fn return_type(&self) -> &Type {
         &self.return_type
     }
+
+    pub fn body(&self) -> Option<&Element> {
+        self.body.as_deref()
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn docs(&self) -> &Vec<String> {
+        &self.docs
+    }
+}
+
+/// Parameter of a function with its type, like "a: int"
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Parameter {
+    name: Token,
+    type_: Type,
+    span: Span,
+}
+
+impl Parameter {
+    pub fn new(name: Token, type_: Type, span: Span) -> Self {
+        Self {
+            name,
+            type_,
+            span,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    pub fn type_(&self) -> &Type {
+        &self.type_
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
 }
@@ -2,28 +2,70 @@
 // Under the MIT License
 // Copyright (c) Junon, Antonin Hérault
 
+pub mod assembly;
+pub mod call;
+pub mod condition;
 pub mod function;
 pub mod operation;
 pub mod type_;
 pub mod variable;
 
+use crate::lang::span::Span;
 use crate::lang::tokens::Token;
 
+use assembly::Assembly;
+use call::Call;
+use condition::Condition;
 use function::Function;
-use operation::Operation;
+use operation::{Operation, UnaryOperation};
 use type_::Type;
 use variable::Variable;
 
 /// Language's element that parsed will create from the tokens
+///
+/// Each element knows the location of the source code it covers
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Element {
-    Array(Vec<Token>),
-    Assembly(Token),
-    Expression(Vec<Element>),
+    /// Parsed values, without the commas
+    Array(Vec<Element>, Span),
+    Assembly(Assembly),
+    Call(Call),
+    Condition(Condition),
+    Expression(Vec<Element>, Span),
+    /// "loop" with its block, always `Element::Expression`
+    Loop(Box<Element>, Span),
     Operation(Operation),
+    UnaryOperation(UnaryOperation),
     Function(Function),
-    Parameters(Vec<Element>),
-    Return(Token),
+    Parameters(Vec<Element>, Span),
+    /// "ret" with its value
+    Return(Option<Box<Element>>, Span),
     Variable(Variable),
-    Other(Token),
+    Other(Token, Span),
+    /// Malformed source code, skipped until the next line, block or 
+    /// declaration
+    ///
+    /// The error is given by `Parser::errors()`
+    Error(Span),
+}
+
+impl Element {
+    pub fn span(&self) -> Span {
+        match *self {
+            Self::Assembly(ref assembly) => assembly.span(),
+            Self::Call(ref call) => call.span(),
+            Self::Condition(ref condition) => condition.span(),
+            Self::Operation(ref operation) => operation.span(),
+            Self::UnaryOperation(ref operation) => operation.span(),
+            Self::Function(ref function) => function.span(),
+            Self::Variable(ref variable) => variable.span(),
+            Self::Array(_, span)
+                | Self::Expression(_, span)
+                | Self::Loop(_, span)
+                | Self::Parameters(_, span)
+                | Self::Return(_, span)
+                | Self::Other(_, span)
+                | Self::Error(span) => span,
+        }
+    }
 }
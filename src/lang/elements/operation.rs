@@ -2,21 +2,33 @@
 // Under the MIT License
 // Copyright (c) Junon, Antonin Hérault
 
+use crate::lang::span::Span;
 use crate::lang::tokens::Token;
+use super::Element;
 
+/// Binary operation, its arguments can be other operations following the
+/// precedence and the associativity of the operators
+///
+/// Example : "1 + 2 * 3" is "+" with the arguments "1" and "2 * 3"
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Operation {
     operator: Token,
-    arg1: Token,
-    arg2: Token,
+    arg1: Box<Element>,
+    arg2: Box<Element>,
+    span: Span,
 }
 
 impl Operation {
-    pub fn new(operator: Token, arg1: Token, arg2: Token) -> Self {
+    /// The arguments are literals and identifiers as `Element::Other`, 
+    /// calls, other operations, expressions or arrays
+    ///
+    /// A missing argument is `Element::Other` with `Token::None`
+    pub fn new(operator: Token, arg1: Element, arg2: Element, span: Span) -> Self {
         Self {
             operator,
-            arg1,
-            arg2,
+            arg1: Box::new(arg1),
+            arg2: Box::new(arg2),
+            span,
         }
     }
 
@@ -24,11 +36,56 @@ impl Operation {
         &self.operator
     }
 
-    pub fn arg1(&self) -> &Token {
+    pub fn arg1(&self) -> &Element {
         &self.arg1
     }
 
-    pub fn arg2(&self) -> &Token {
+    pub fn arg2(&self) -> &Element {
         &self.arg2
     }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Operator, arguments and location, moved out of the object
+    pub fn into_parts(self) -> (Token, Element, Element, Span) {
+        (self.operator, *self.arg1, *self.arg2, self.span)
+    }
+}
+
+/// Prefix operation with its operand, like "-a" or "!(a == b)"
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UnaryOperation {
+    operator: Token,
+    arg: Box<Element>,
+    span: Span,
+}
+
+impl UnaryOperation {
+    /// A missing argument is `Element::Other` with `Token::None`
+    pub fn new(operator: Token, arg: Element, span: Span) -> Self {
+        Self {
+            operator,
+            arg: Box::new(arg),
+            span,
+        }
+    }
+
+    pub fn operator(&self) -> &Token {
+        &self.operator
+    }
+
+    pub fn arg(&self) -> &Element {
+        &self.arg
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Operator, argument and location, moved out of the object
+    pub fn into_parts(self) -> (Token, Element, Span) {
+        (self.operator, *self.arg, self.span)
+    }
 }
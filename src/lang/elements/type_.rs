@@ -2,6 +2,8 @@
 // Under the MIT License
 // Copyright (c) Junon, Antonin Hérault
 
+use std::fmt;
+
 use x64asm::ddirective;
 use x64asm::ddirective::DefineDirective::*;
 use x64asm::operand::Operand;
@@ -21,28 +23,41 @@ pub enum Type {
 }
 
 impl Type {
-    pub fn from_string(type_as_string: String) -> Self {
+    /// Returns `None` when the string is not a type name
+    pub fn from_string(type_as_string: String) -> Option<Self> {
         match type_as_string.as_str() {
-            "byte" => Type::Byte,
-            "int" => Type::Integer,
-            "bigint" => Type::BigInteger,
-            "" => Type::None,
-            _ => panic!(),
+            "byte" => Some(Type::Byte),
+            "int" => Some(Type::Integer),
+            "bigint" => Some(Type::BigInteger),
+            "" => Some(Type::None),
+            _ => None,
         }
     }
 
-    pub fn array_from_string(type_as_string: String, len: usize) -> Self {
-        let type_ = Self::from_string(type_as_string);
-        Type::Array(Box::new(type_), len)
+    pub fn array_from_string(type_as_string: String, len: usize) -> Option<Self> {
+        let type_ = Self::from_string(type_as_string)?;
+        Some(Type::Array(Box::new(type_), len))
     }
 
     pub fn new_array(type_: Type, len: usize) -> Self {
         Type::Array(Box::new(type_), len)
     }
 
-    pub fn static_array_from_string(type_as_string: String) -> Self {
-        let type_ = Self::from_string(type_as_string);
-        Type::StaticArray(Box::new(type_))
+    pub fn static_array_from_string(type_as_string: String) -> Option<Self> {
+        let type_ = Self::from_string(type_as_string)?;
+        Some(Type::StaticArray(Box::new(type_)))
+    }
+
+    /// Type name that would be written as suffix of an integer, like "42byte"
+    pub fn from_suffix(number: &str) -> (&str, Self) {
+        // "bigint" before "int" because it ends with "int"
+        for suffix in ["bigint", "byte", "int"] {
+            if let Some(number) = number.strip_suffix(suffix) {
+                let type_ = Self::from_string(suffix.to_string()).unwrap_or(Self::None);
+                return (number, type_);
+            }
+        }
+        (number, Self::None)
     }
 
     pub fn to_asm_operand(&self) -> Operand {
@@ -68,3 +83,17 @@ impl Type {
         }
     }
 }
+
+impl fmt::Display for Type {
+    /// Written as it would be in the source code, nothing for `Type::None`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Self::Byte => write!(f, "byte"),
+            Self::Integer => write!(f, "int"),
+            Self::BigInteger => write!(f, "bigint"),
+            Self::StaticArray(ref type_) => write!(f, "{}[]", type_),
+            Self::Array(ref type_, len) => write!(f, "{}[{}]", type_, len),
+            Self::None => Ok(()),
+        }
+    }
+}
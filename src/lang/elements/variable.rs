@@ -2,24 +2,37 @@
 // Under the MIT License
 // Copyright (c) Junon, Antonin Hérault
 
+use crate::lang::span::Span;
 use crate::lang::tokens::Token;
-use super::type_::Type;
+use super::{
+    Element,
+    type_::Type
+};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Variable {
     id: Token,
     type_: Type,
-    value: Token,
+    /// `None` when the variable is only declared
+    value: Option<Box<Element>>,
     stack_pos: usize,
+    span: Span,
+    /// Declared with `Token::Static` instead of `Token::Variable`
+    is_static: bool,
+    /// Lines of the "///" comments written before the variable
+    docs: Vec<String>,
 }
 
 impl Variable {
-    pub fn new(id: Token, type_: Type, value: Token) -> Self {
+    pub fn new(id: Token, type_: Type, span: Span) -> Self {
         Self {
             id,
             type_,
-            value,
+            value: None,
             stack_pos: 0, // changed by the compiler
+            span,
+            is_static: false,
+            docs: vec![],
         }
     }
 
@@ -31,13 +44,29 @@ impl Variable {
         self.type_ = type_;
     }
 
-    pub fn set_value(&mut self, value: String) {
-        self.value = Token::from_string(value.as_str());
+    /// The variable's location is extended to the value
+    pub fn set_value(&mut self, value: Element) {
+        self.span = self.span.to(&value.span());
+        self.value = Some(Box::new(value));
+    }
+
+    /// Takes the value out of the variable, its location still covers the 
+    /// value
+    pub fn take_value(&mut self) -> Option<Element> {
+        self.value.take().map(|value| *value)
     }
 
     pub fn set_stack_pos(&mut self, stack_pos: usize) {
         self.stack_pos = stack_pos;
     }
+
+    pub fn set_static(&mut self, is_static: bool) {
+        self.is_static = is_static;
+    }
+
+    pub fn set_docs(&mut self, docs: Vec<String>) {
+        self.docs = docs;
+    }
     
     pub fn id(&self) -> String {
         self.id.to_string()
@@ -47,11 +76,23 @@ impl Variable {
         &self.type_
     }
 
-    pub fn value(&self) -> &Token {
-        &self.value
+    pub fn value(&self) -> Option<&Element> {
+        self.value.as_deref()
     }
 
     pub fn stack_pos(&self) -> usize {
         self.stack_pos
     }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn is_static(&self) -> bool {
+        self.is_static
+    }
+
+    pub fn docs(&self) -> &Vec<String> {
+        &self.docs
+    }
 }
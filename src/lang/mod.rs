@@ -2,7 +2,11 @@
 // Under the MIT License
 // Copyright (c) Junon, Antonin Hérault
 
+pub mod ast;
 pub mod elements;
 
 pub mod rules;
+pub mod span;
+pub mod symbol;
 pub mod tokens;
+pub mod version;
@@ -3,6 +3,7 @@
 // Copyright (c) Junon, Antonin Hérault
 
 use crate::lang::tokens::Token;
+use crate::lang::version::LanguageVersion;
 
 #[derive(Debug)]
 pub enum RuleItem {
@@ -71,6 +72,21 @@ impl Rule {
         ]
     }
 
+    /// Make a series of possibilities for an assignment operator
+    ///
+    /// Example : "a += 5" where the rule is made for "+="
+    fn from_assignment(op: &Token) -> Vec<Self> {
+        vec![
+            rule!(RuleItem::Label, RuleItem::Main(op.clone()), RuleItem::Value),
+            rule!(RuleItem::Label, RuleItem::Main(op.clone()), RuleItem::Label),
+            rule!(
+                RuleItem::Label,
+                RuleItem::Main(op.clone()),
+                RuleItem::Expression
+            ),
+        ]
+    }
+
     fn from_basic_scheme(first: &Token) -> Vec<Self> {
         vec![
             rule!(RuleItem::Main(first.clone()), RuleItem::Value),
@@ -79,33 +95,36 @@ impl Rule {
         ]
     }
 
-    /// Gets a list of rules to be used with a token
+    /// Gets a list of rules to be used with a token, in the latest version of
+    /// the language
     pub fn from_token(token: Token) -> Vec<Self> {
+        Self::from_token_in(token, LanguageVersion::LATEST)
+    }
+
+    /// Gets a list of rules to be used with a token in the given version, 
+    /// empty when the token does not exist in this version
+    pub fn from_token_in(token: Token, version: LanguageVersion) -> Vec<Self> {
+        if !version.has_token(&token) {
+            return vec![];
+        }
+
         match token {
+            ref token if token.is_assignment() => Self::from_assignment(token),
+            ref token if token.is_binary_operator() => Self::from_operator(token),
             Token::Assembly => {
-                vec![
+                let mut rules = vec![
                     rule!(RuleItem::Main(Token::Assembly)),
                     rule!(RuleItem::Main(Token::Assembly), RuleItem::Value),
-                ]
-            }
-            Token::Assign => {
-                vec![
-                    rule!(
-                        RuleItem::Label,
-                        RuleItem::Main(Token::Assign),
-                        RuleItem::Value
-                    ),
-                    rule!(
-                        RuleItem::Label,
-                        RuleItem::Main(Token::Assign),
-                        RuleItem::Label
-                    ),
-                    rule!(
-                        RuleItem::Label,
-                        RuleItem::Main(Token::Assign),
-                        RuleItem::Expression
-                    ),
-                ]
+                ];
+                if version.has_assembly_blocks() {
+                    rules.push(rule!(
+                        RuleItem::Main(Token::Assembly),
+                        RuleItem::Token(Token::BracketOpen),
+                        RuleItem::Skip,
+                        RuleItem::Token(Token::BracketClose)
+                    ));
+                }
+                rules
             }
             Token::ConditionElse => {
                 vec![
@@ -129,7 +148,6 @@ impl Rule {
                     )
                 ]
             }
-            Token::Divide => Self::from_operator(&Token::Divide),
             Token::Function => {
                 vec![
                     rule!(RuleItem::Main(Token::Function), RuleItem::Label),
@@ -142,8 +160,6 @@ impl Rule {
                     ),
                 ]
             }
-            Token::LessThan => Self::from_operator(&Token::LessThan),
-            Token::LessThanOrEqual => Self::from_operator(&Token::LessThanOrEqual),
             Token::Loop => {
                 vec![
                     rule!(
@@ -172,10 +188,6 @@ impl Rule {
                     ),
                 ]
             }
-            Token::Minus => Self::from_operator(&Token::Minus),
-            Token::MoreThan => Self::from_operator(&Token::MoreThan),
-            Token::MoreThanOrEqual => Self::from_operator(&Token::MoreThanOrEqual),
-            Token::Multiply => Self::from_operator(&Token::Multiply),
             Token::Point => {
                 vec![rule!(
                     RuleItem::Label,
@@ -183,7 +195,6 @@ impl Rule {
                     RuleItem::Label
                 )]
             }
-            Token::Plus => Self::from_operator(&Token::Plus),
             Token::Return => Self::from_basic_scheme(&Token::Return),
             Token::Static => {
                 vec![
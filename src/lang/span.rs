@@ -0,0 +1,59 @@
+// This file is part of "jup"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+/// Location of a piece of source code, for a token or an element
+///
+/// Lines and columns start from `1`, columns are counted in characters and not
+/// in bytes (see `Tokenizer::set_tab_width()` for the tabs)
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct Span {
+    /// Byte offset of the first character
+    start: usize,
+    /// Byte offset just after the last character
+    end: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize, line: usize, column: usize) -> Self {
+        Self {
+            start,
+            end,
+            line,
+            column,
+        }
+    }
+
+    /// Creates a span going from the start of `self` to the end of `other`
+    pub fn to(&self, other: &Span) -> Self {
+        Self {
+            start: self.start,
+            end: self.end.max(other.end),
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Byte range to be used for slicing the source code
+    pub fn range(&self) -> std::ops::Range<usize> {
+        self.start..self.end
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
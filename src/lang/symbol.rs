@@ -0,0 +1,136 @@
+// This file is part of "jup"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Name of an identifier, shared between all the tokens with the same name
+/// when created by a `SymbolTable`
+///
+/// Cloning a symbol does not allocate
+#[derive(Clone, PartialOrd, Ord)]
+pub struct Symbol(Arc<str>);
+
+impl Symbol {
+    pub fn new(string: &str) -> Self {
+        Self(Arc::from(string))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq for Symbol {
+    /// Interned symbols are the same allocation, the strings are only compared
+    /// for symbols coming from different tables
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0) || self.0 == other.0
+    }
+}
+
+impl Eq for Symbol {}
+
+impl Hash for Symbol {
+    /// Same hash as the string, for `Borrow<str>`
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl Deref for Symbol {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Symbol {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(string: &str) -> Self {
+        Self::new(string)
+    }
+}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.as_str())
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Names of the identifiers found by the tokenizer, each name is allocated
+/// only once
+#[derive(Debug, Default)]
+pub struct SymbolTable {
+    symbols: HashSet<Symbol>,
+}
+
+impl SymbolTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the symbol of the name, created the first time the name is seen
+    pub fn intern(&mut self, name: &str) -> Symbol {
+        if let Some(symbol) = self.symbols.get(name) {
+            return symbol.clone();
+        }
+
+        let symbol = Symbol::new(name);
+        self.symbols.insert(symbol.clone());
+        symbol
+    }
+
+    /// Count of different names
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+}
+
+#[test]
+fn intern() {
+    let mut symbols = SymbolTable::new();
+
+    let a = symbols.intern("foo");
+    let b = symbols.intern("foo");
+    let c = symbols.intern("bar");
+
+    assert!(Arc::ptr_eq(&a.0, &b.0));
+    assert_eq!(a, Symbol::new("foo"));
+    assert_ne!(a, c);
+    assert_eq!(symbols.len(), 2);
+    assert_eq!(&*c, "bar");
+}
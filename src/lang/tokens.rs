@@ -2,21 +2,84 @@
 // Under the MIT License
 // Copyright (c) Junon, Antonin Hérault
 
+use std::fmt;
 use std::string::ToString;
 
+use unicode_xid::UnicodeXID;
+
+use crate::lang::elements::type_::Type;
+use crate::lang::span::Span;
+use crate::lang::symbol::{Symbol, SymbolTable};
+use crate::lang::version::LanguageVersion;
+
+/// Operators and punctuation tokens as written in the source code, the 
+/// tokenizer always takes the longest one matching the source code
+///
+/// A new operator token only has to be written here, in `Token::to_string()` 
+/// and in `Token::token_from_str()` to be found by the tokenizer, then in 
+/// `Token::category()` and `Token::precedence()` to be known by the parser
+pub const OPERATORS: &[&str] = &[
+    "&&", "->", "=", "{", "}", ",", "//", "/", "/=", "::", "==", "<", "<=", "-",
+    "-=", ">", ">=", "*", "*=", "!", "!=", "||", "(", ")", ".", "+", "+=", ";", 
+    "<<", ">>", "[", "]", ":", 
+];
+
+/// Keywords as written in the source code, they cannot be used as identifiers
+///
+/// These are the keywords of the latest version, see `LanguageVersion` for the
+/// older ones
+pub const KEYWORDS: &[&str] = &[
+    "else", "if", "fun", "loop", "break", "continue", "ret", "static", "let",
+    "@",
+];
+
+/// Kind of a token, for tools like highlighters
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TokenCategory {
+    /// Like "fun", "let" or "@"
+    Keyword,
+    /// Like "+", "==" or "."
+    Operator,
+    /// Brackets and separators, like "{", "," or ":"
+    Delimiter,
+    Identifier,
+    Literal,
+    /// Comments and new lines, meaningless for the compiler
+    Trivia,
+    /// Assembly code and invalid tokens
+    Other,
+}
+
+/// Side from where the operators of a same precedence are grouped
+///
+/// Example : "a - b - c" is "(a - b) - c" because "-" is left associative
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Associativity {
+    Left,
+    Right,
+}
+
 /// All tokens list for the Junon programming
 #[allow(unused)] // for debug
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Token {
+    And,
+    Arrow,
     Assembly,
     Assign,
     BracketOpen,
     BracketClose,
     Comma,
     Comment,
+    /// Text of a "///" comment, documenting the following element
+    DocComment(String),
+    /// Text of a "//!" comment, documenting the whole module
+    ModuleDocComment(String),
     ConditionElse,
     ConditionIf,
     Divide,
+    DivideAssign,
+    DoubleColon,
     Equal,
     Function,
     LessThan,
@@ -25,15 +88,23 @@ pub enum Token {
     LoopBreak,
     LoopContinue,
     Minus,
+    MinusAssign,
     MoreThan,
     MoreThanOrEqual,
     Multiply,
+    MultiplyAssign,
+    Not,
+    NotEqual,
+    Or,
     ParenOpen,
     ParenClose,
     Point,
     Plus,
+    PlusAssign,
     Return,
     SemiColon,
+    ShiftLeft,
+    ShiftRight,
     SquareBracketOpen,
     SquareBracketClose,
     Static,
@@ -41,8 +112,23 @@ pub enum Token {
     TypeDef,
     Variable,
 
-    /// Example : "foo" or something like that is not a real token
-    /// Note : It can be a string, an value like an integer or a float
+    /// Name of a variable, a function or a type, like "foo" or "int"
+    Ident(Symbol),
+    /// Decoded value of an integer, like "42", "0xFF" or "1_000", with the
+    /// type given as suffix like "42byte" (`Type::None` when there is no 
+    /// suffix)
+    IntLiteral(u64, Type),
+    /// Decoded value of a floating-point number, like "3.14" or "1.5e-3"
+    FloatLiteral(Float),
+    /// Decoded content of a string between `Token::StringDot`, like "'abc'"
+    StringLiteral(String),
+    /// A string containing exactly one character, like "'a'"
+    CharLiteral(char),
+    /// Raw line of assembly code following a `Token::Assembly`, or written in
+    /// an assembly block "@{ ... }"
+    AssemblyCode(String),
+
+    /// Something that is not a real token, like "$"
     Other(String),
     // Avoid using a 2D table and permits to do not respect line breaks
     NewLine,
@@ -53,16 +139,26 @@ pub enum Token {
 impl ToString for Token {
     /// A void string is returned when it cannot be converted to `String`
     fn to_string(&self) -> String {
+        let string_dot = "'";
+
         match *self {
+            Self::And => "&&",
+            Self::Arrow => "->",
             Self::Assembly => "@",
             Self::Assign => "=",
             Self::BracketOpen => "{",
             Self::BracketClose => "}",
             Self::Comma => ",",
             Self::Comment => "//",
+            Self::DocComment(ref text) => return format!("///{}", Self::comment_from_doc(text)),
+            Self::ModuleDocComment(ref text) => {
+                return format!("//!{}", Self::comment_from_doc(text))
+            }
             Self::ConditionElse => "else",
             Self::ConditionIf => "if",
             Self::Divide => "/",
+            Self::DivideAssign => "/=",
+            Self::DoubleColon => "::",
             Self::Equal => "==",
             Self::Function => "fun",
             Self::LessThan => "<",
@@ -71,23 +167,44 @@ impl ToString for Token {
             Self::LoopBreak => "break",
             Self::LoopContinue => "continue",
             Self::Minus => "-",
+            Self::MinusAssign => "-=",
             Self::MoreThan => ">",
             Self::MoreThanOrEqual => ">=",
             Self::Multiply => "*",
+            Self::MultiplyAssign => "*=",
+            Self::Not => "!",
+            Self::NotEqual => "!=",
+            Self::Or => "||",
             Self::ParenOpen => "(",
             Self::ParenClose => ")",
             Self::Point => ".",
             Self::Plus => "+",
+            Self::PlusAssign => "+=",
             Self::Return => "ret",
             Self::SemiColon => ";",
+            Self::ShiftLeft => "<<",
+            Self::ShiftRight => ">>",
             Self::SquareBracketOpen => "[",
             Self::SquareBracketClose => "]",
             Self::Static => "static",
-            Self::StringDot => "'",
+            Self::StringDot => string_dot,
             Self::TypeDef => ":",
             Self::Variable => "let",
 
-            Self::Other(ref string) => &*string,
+            Self::Ident(ref symbol) => symbol,
+            Self::IntLiteral(value, ref type_) => {
+                return format!("{}{}", value, type_)
+            }
+            Self::FloatLiteral(value) => return value.to_string(),
+            Self::StringLiteral(ref string) => {
+                return format!("{}{}{}", string_dot, Self::escape(string), string_dot)
+            }
+            Self::CharLiteral(c) => {
+                return format!("{}{}{}", string_dot, Self::escape(&c.to_string()), string_dot)
+            }
+
+            Self::AssemblyCode(ref code) => code,
+            Self::Other(ref string) => string,
             Self::NewLine => "\n",
             Self::None => "",
         }
@@ -97,10 +214,41 @@ impl ToString for Token {
 
 impl Token {
     /// Convert a string into a `Token` object \
-    /// If the string does not correspond to any token, it will return a
-    /// `Token::Other` object with contained string into
+    /// If the string does not correspond to any token, identifier or literal, 
+    /// it will return a `Token::Other` object with contained string into
     pub fn token_from_str(string: &str) -> Self {
-        match string {
+        Self::token_from_str_in(string, LanguageVersion::LATEST)
+    }
+
+    /// Same as `token_from_str()` for a version of the language, the keywords
+    /// of the other versions are identifiers
+    pub fn token_from_str_in(string: &str, version: LanguageVersion) -> Self {
+        Self::fixed_from_str(string, version)
+            .unwrap_or_else(|| Self::literal_from_str(string, None))
+    }
+
+    /// Same as `token_from_str_in()`, but the identifiers are interned in 
+    /// `symbols` to allocate each name only once
+    pub fn from_str_interned(
+        string: &str, 
+        symbols: &mut SymbolTable, 
+        version: LanguageVersion
+    ) -> Self {
+        Self::fixed_from_str(string, version)
+            .unwrap_or_else(|| Self::literal_from_str(string, Some(symbols)))
+    }
+
+    /// Keywords, operators and punctuation, always written the same way
+    fn fixed_from_str(string: &str, version: LanguageVersion) -> Option<Self> {
+        // Only the older versions miss some of them
+        if version != LanguageVersion::LATEST && !version.has_str(string) {
+            return None;
+        }
+
+
+        let token = match string {
+            "&&" => Self::And,
+            "->" => Self::Arrow,
             "@" => Self::Assembly,
             "=" => Self::Assign,
             "{" => Self::BracketOpen,
@@ -110,6 +258,8 @@ impl Token {
             "else" => Self::ConditionElse,
             "if" => Self::ConditionIf,
             "/" => Self::Divide,
+            "/=" => Self::DivideAssign,
+            "::" => Self::DoubleColon,
             "==" => Self::Equal,
             "fun" => Self::Function,
             "<" => Self::LessThan,
@@ -118,15 +268,23 @@ impl Token {
             "break" => Self::LoopBreak,
             "continue" => Self::LoopContinue,
             "-" => Self::Minus,
+            "-=" => Self::MinusAssign,
             ">" => Self::MoreThan,
             ">=" => Self::MoreThanOrEqual,
             "*" => Self::Multiply,
+            "*=" => Self::MultiplyAssign,
+            "!" => Self::Not,
+            "!=" => Self::NotEqual,
+            "||" => Self::Or,
             "(" => Self::ParenOpen,
             ")" => Self::ParenClose,
             "." => Self::Point,
             "+" => Self::Plus,
+            "+=" => Self::PlusAssign,
             "ret" => Self::Return,
             ";" => Self::SemiColon,
+            "<<" => Self::ShiftLeft,
+            ">>" => Self::ShiftRight,
             "[" => Self::SquareBracketOpen,
             "]" => Self::SquareBracketClose,
             "static" => Self::Static,
@@ -135,10 +293,394 @@ impl Token {
             "let" => Self::Variable,
 
             "\n" => Self::NewLine,
+            _ => return None,
+        };
+        Some(token)
+    }
+
+    /// Identifiers and literals are not fixed strings, they are recognized by
+    /// their shape
+    ///
+    /// Identifiers are interned in `symbols` when given
+    fn literal_from_str(string: &str, symbols: Option<&mut SymbolTable>) -> Self {
+        let string_dot = '\'';
+
+        // A string or a character
+        if string.len() >= 2 
+            && string.starts_with(string_dot) 
+            && string.ends_with(string_dot) 
+        {
+            return match Self::unescape(&string[1..string.len() - 1]) {
+                Ok(content) => Self::from_string_content(&content),
+                Err(_) => Self::Other(string.to_string()),
+            };
+        }
+
+        let mut chars = string.chars();
+        match chars.next() {
+            // Identifiers follow the Unicode XID rules
+            Some(first) if first.is_xid_start() || first == '_' => {
+                if !chars.all(|c| c.is_xid_continue()) {
+                    Self::Other(string.to_string())
+                } else if let Some(symbols) = symbols {
+                    Self::Ident(symbols.intern(string))
+                } else {
+                    Self::Ident(Symbol::new(string))
+                }
+            }
+            Some(first) if first.is_ascii_digit() => {
+                Self::number_from_str(string)
+                    .unwrap_or_else(|_| Self::Other(string.to_string()))
+            }
             _ => Self::Other(string.to_string()),
         }
     }
 
+    /// Converts a number written in the source code to a `Token::IntLiteral`
+    /// or a `Token::FloatLiteral`
+    ///
+    /// Integers can be written in hexadecimal ("0xFF"), binary ("0b101") or 
+    /// octal ("0o17"), digits can be separated by underscores ("1_000") and 
+    /// a type can be given as suffix ("255byte", "1_int", "0xFFbigint") \
+    /// Floating-point numbers are only decimal ("3.14", "1e10", "2.5E-3")
+    ///
+    /// If the number is malformed, the function will returns the error message
+    pub fn number_from_str(string: &str) -> Result<Self, String> {
+        let (number, type_) = Type::from_suffix(string);
+
+        let (digits, radix) = match number.get(..2) {
+            Some("0x") => (&number[2..], 16),
+            Some("0b") => (&number[2..], 2),
+            Some("0o") => (&number[2..], 8),
+            _ => (number, 10),
+        };
+
+        let digits: String = digits.chars().filter(|c| *c != '_').collect();
+        if digits.is_empty() {
+            return Err(format!("Missing digits in number '{}'", string));
+        }
+
+        let is_float = radix == 10 && digits.contains(['.', 'e', 'E']);
+        if is_float {
+            if type_ != Type::None {
+                return Err(format!(
+                    "Floating-point number '{}' cannot have a type suffix", 
+                    string
+                ));
+            }
+
+            let is_valid = digits
+                .chars()
+                .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-'));
+
+            return match digits.parse::<f64>() {
+                // Too big numbers are parsed as the infinity
+                Ok(value) if is_valid && !value.is_finite() => Err(format!(
+                    "Floating-point number '{}' is out of range",
+                    string
+                )),
+                Ok(value) if is_valid => Ok(Self::FloatLiteral(Float::new(value))),
+                _ => Err(format!("Invalid floating-point number '{}'", string)),
+            };
+        }
+
+        if let Some(c) = digits.chars().find(|c| !c.is_digit(radix)) {
+            return Err(format!("Invalid digit '{}' in number '{}'", c, string));
+        }
+
+        let value = u64::from_str_radix(&digits, radix)
+            .map_err(|_| format!("Number '{}' is too big", string))?;
+
+        let max = match type_ {
+            Type::Byte => u8::MAX as u64,
+            Type::Integer => u32::MAX as u64,
+            _ => u64::MAX,
+        };
+        if value > max {
+            return Err(format!(
+                "Number '{}' is too big for type '{}'", 
+                string, 
+                type_
+            ));
+        }
+
+        Ok(Self::IntLiteral(value, type_))
+    }
+
+    /// Creates the literal for a content written between `Token::StringDot`,
+    /// a `Token::CharLiteral` when it contains only one character
+    pub fn from_string_content(content: &str) -> Self {
+        let mut chars = content.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::CharLiteral(c),
+            _ => Self::StringLiteral(content.to_string()),
+        }
+    }
+
+    /// Decodes the escape sequences of a string written between 
+    /// `Token::StringDot` : "\\n", "\\r", "\\t", "\\\\", "\\'", "\\0", "\\x7F" and
+    /// "\\u{1F600}"
+    ///
+    /// If an escape sequence is invalid, the function will returns the error
+    /// message
+    pub fn unescape(raw: &str) -> Result<String, String> {
+        let mut content = String::new();
+        let mut chars = raw.chars();
+
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                content.push(c);
+                continue;
+            }
+
+            let escaped = match chars.next() {
+                Some('n') => '\n',
+                Some('r') => '\r',
+                Some('t') => '\t',
+                Some('\\') => '\\',
+                Some('\'') => '\'',
+                Some('0') => '\0',
+                Some('x') => {
+                    let digits: String = chars.by_ref().take(2).collect();
+                    let is_valid = digits.len() == 2 
+                        && digits.chars().all(|c| c.is_ascii_hexdigit());
+
+                    match u8::from_str_radix(&digits, 16) {
+                        Ok(value) if is_valid && value <= 0x7F => value as char,
+                        _ => {
+                            return Err(format!(
+                                "Invalid escape sequence '\\x{}', expected a value up to 7F", 
+                                digits
+                            ))
+                        }
+                    }
+                }
+                Some('u') => {
+                    let mut sequence = String::new();
+                    let mut is_closed = false;
+
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            is_closed = true;
+                            break;
+                        }
+                        sequence.push(c);
+                    }
+
+                    let digits = sequence.strip_prefix('{').unwrap_or("");
+                    let is_valid = is_closed 
+                        && digits.len() <= 6 
+                        && digits.chars().all(|c| c.is_ascii_hexdigit());
+
+                    match u32::from_str_radix(digits, 16).ok().and_then(char::from_u32) {
+                        Some(c) if is_valid => c,
+                        _ => {
+                            return Err(format!(
+                                "Invalid escape sequence '\\u{}}}', expected a unicode value", 
+                                sequence
+                            ))
+                        }
+                    }
+                }
+                Some(other) => {
+                    return Err(format!("Unknown escape sequence '\\{}'", other))
+                }
+                None => return Err("Unfinished escape sequence '\\'".to_string()),
+            };
+            content.push(escaped);
+        }
+
+        Ok(content)
+    }
+
+    /// Inverse of `Token::unescape()`, to write the content back between
+    /// `Token::StringDot`
+    pub fn escape(content: &str) -> String {
+        let mut raw = String::new();
+
+        for c in content.chars() {
+            match c {
+                '\n' => raw.push_str("\\n"),
+                '\r' => raw.push_str("\\r"),
+                '\t' => raw.push_str("\\t"),
+                '\\' => raw.push_str("\\\\"),
+                '\'' => raw.push_str("\\'"),
+                '\0' => raw.push_str("\\0"),
+                c if c.is_control() => raw.push_str(&format!("\\u{{{:x}}}", c as u32)),
+                c => raw.push(c),
+            }
+        }
+
+        raw
+    }
+
+    /// The text of a documentation comment without its first space, like 
+    /// "/// Foo" giving "Foo"
+    pub fn doc_from_comment(comment: &str) -> String {
+        comment.strip_prefix(' ').unwrap_or(comment).to_string()
+    }
+
+    /// Inverse of `Token::doc_from_comment()`
+    fn comment_from_doc(text: &str) -> String {
+        if text.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", text)
+        }
+    }
+
+    /// All the keywords tokens, in the same order as `KEYWORDS`
+    pub fn keywords() -> impl Iterator<Item = Self> {
+        KEYWORDS.iter().map(|keyword| Self::token_from_str(keyword))
+    }
+
+    pub fn category(&self) -> TokenCategory {
+        match *self {
+            Self::Assembly
+                | Self::ConditionElse
+                | Self::ConditionIf
+                | Self::Function
+                | Self::Loop
+                | Self::LoopBreak
+                | Self::LoopContinue
+                | Self::Return
+                | Self::Static
+                | Self::Variable => TokenCategory::Keyword,
+            Self::And
+                | Self::Assign
+                | Self::Divide
+                | Self::DivideAssign
+                | Self::DoubleColon
+                | Self::Equal
+                | Self::LessThan
+                | Self::LessThanOrEqual
+                | Self::Minus
+                | Self::MinusAssign
+                | Self::MoreThan
+                | Self::MoreThanOrEqual
+                | Self::Multiply
+                | Self::MultiplyAssign
+                | Self::Not
+                | Self::NotEqual
+                | Self::Or
+                | Self::Point
+                | Self::Plus
+                | Self::PlusAssign
+                | Self::ShiftLeft
+                | Self::ShiftRight => TokenCategory::Operator,
+            Self::Arrow
+                | Self::BracketOpen
+                | Self::BracketClose
+                | Self::Comma
+                | Self::ParenOpen
+                | Self::ParenClose
+                | Self::SemiColon
+                | Self::SquareBracketOpen
+                | Self::SquareBracketClose
+                | Self::StringDot
+                | Self::TypeDef => TokenCategory::Delimiter,
+            Self::Ident(_) => TokenCategory::Identifier,
+            Self::IntLiteral(..)
+                | Self::FloatLiteral(_)
+                | Self::StringLiteral(_)
+                | Self::CharLiteral(_) => TokenCategory::Literal,
+            Self::Comment
+                | Self::DocComment(_)
+                | Self::ModuleDocComment(_)
+                | Self::NewLine => TokenCategory::Trivia,
+            Self::AssemblyCode(_) | Self::Other(_) | Self::None => TokenCategory::Other,
+        }
+    }
+
+    /// Precedence of a binary operator, the highest is applied first \
+    /// Returns `None` when the token is not a binary operator
+    ///
+    /// Example : "a + b * c" is "a + (b * c)" because "*" has a higher 
+    /// precedence than "+"
+    pub fn precedence(&self) -> Option<u8> {
+        let precedence = match *self {
+            Self::Assign
+                | Self::PlusAssign
+                | Self::MinusAssign
+                | Self::MultiplyAssign
+                | Self::DivideAssign => 1,
+            Self::Or => 2,
+            Self::And => 3,
+            Self::Equal | Self::NotEqual => 4,
+            Self::LessThan
+                | Self::LessThanOrEqual
+                | Self::MoreThan
+                | Self::MoreThanOrEqual => 5,
+            Self::ShiftLeft | Self::ShiftRight => 6,
+            Self::Plus | Self::Minus => 7,
+            Self::Multiply | Self::Divide => 8,
+            _ => return None,
+        };
+        Some(precedence)
+    }
+
+    /// Associativity of a binary operator, assignments are right associative
+    /// ("a = b = c" is "a = (b = c)") and the others are left associative \
+    /// Returns `None` when the token is not a binary operator
+    pub fn associativity(&self) -> Option<Associativity> {
+        match self.precedence() {
+            Some(_) if self.is_assignment() => Some(Associativity::Right),
+            Some(_) => Some(Associativity::Left),
+            None => None,
+        }
+    }
+
+    pub fn is_binary_operator(&self) -> bool {
+        self.precedence().is_some()
+    }
+
+    /// Precedence of a prefix operator, like "-" in "-a" or "!" in "!a", 
+    /// tighter than the binary operators ("-a * b" is "(-a) * b") \
+    /// Returns `None` when the token is not a prefix operator
+    pub fn prefix_precedence(&self) -> Option<u8> {
+        match *self {
+            Self::Minus | Self::Not => Some(9),
+            _ => None,
+        }
+    }
+
+    pub fn is_prefix_operator(&self) -> bool {
+        self.prefix_precedence().is_some()
+    }
+
+    /// "=" and the compound assignments like "+="
+    pub fn is_assignment(&self) -> bool {
+        matches!(
+            *self,
+            Self::Assign
+                | Self::PlusAssign
+                | Self::MinusAssign
+                | Self::MultiplyAssign
+                | Self::DivideAssign
+        )
+    }
+
+    pub fn is_keyword(&self) -> bool {
+        self.category() == TokenCategory::Keyword
+    }
+
+    /// Whether the token is a value written as is in the source code
+    pub fn is_literal(&self) -> bool {
+        matches!(
+            *self,
+            Self::IntLiteral(..)
+                | Self::FloatLiteral(_)
+                | Self::StringLiteral(_)
+                | Self::CharLiteral(_)
+        )
+    }
+
+    /// Whether the token is a value by itself, an identifier or a literal
+    pub fn is_value(&self) -> bool {
+        matches!(*self, Self::Ident(_)) || self.is_literal()
+    }
+
     /// The string as `&String` is converted into a `&str` before returning
     /// a `::from_str()` call
     pub fn from_string(string: &str) -> Self {
@@ -146,6 +688,118 @@ impl Token {
     }
 }
 
+/// Decoded value of a `Token::FloatLiteral` \
+/// Compared by its bits, to keep `Token` comparable with `Eq`
+#[derive(Debug, Clone, Copy)]
+pub struct Float(f64);
+
+impl Float {
+    pub fn new(value: f64) -> Self {
+        Self(value)
+    }
+
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+}
+
+impl PartialEq for Float {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+impl Eq for Float {}
+
+impl fmt::Display for Float {
+    /// Always written with a point, to be read back as a float
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+/// Source code ignored by the parser, only kept by the tokenizer in lossless
+/// mode to be able to rewrite the source code as is
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Trivia {
+    /// Spaces and tabulations
+    Whitespace(String),
+    /// Comment with its delimiters, a block comment written on several lines
+    /// is cut by the `Token::NewLine` tokens
+    Comment(String),
+}
+
+impl Trivia {
+    pub fn text(&self) -> &String {
+        match *self {
+            Self::Whitespace(ref text) | Self::Comment(ref text) => text,
+        }
+    }
+}
+
+/// A `Token` with the location where it was found in the source code
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SpannedToken {
+    token: Token,
+    span: Span,
+
+    // Only filled by the tokenizer in lossless mode
+    /// Trivia found between the previous token and this one
+    trivia: Vec<Trivia>,
+    /// Source code of the token
+    text: String,
+}
+
+impl SpannedToken {
+    pub fn new(token: Token, span: Span) -> Self {
+        Self { 
+            token, 
+            span,
+            trivia: vec![],
+            text: String::new(),
+        }
+    }
+
+    pub fn set_trivia(&mut self, trivia: Vec<Trivia>) {
+        self.trivia = trivia;
+    }
+
+    pub fn set_text(&mut self, text: String) {
+        self.text = text;
+    }
+
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn trivia(&self) -> &Vec<Trivia> {
+        &self.trivia
+    }
+
+    pub fn text(&self) -> &String {
+        &self.text
+    }
+
+    /// Trivia followed by the source code of the token
+    pub fn lossless_text(&self) -> String {
+        let mut text: String = self.trivia.iter().map(|trivia| trivia.text().as_str()).collect();
+        text.push_str(&self.text);
+        text
+    }
+}
+
+/// Permits to compare a located token with a token kind, without caring about
+/// the location
+impl PartialEq<Token> for SpannedToken {
+    fn eq(&self, other: &Token) -> bool {
+        self.token == *other
+    }
+}
+
 // Don't forget to add "-- --nocapture" flags to the command line arguments
 // when you execute `cargo test`
 
@@ -166,3 +820,106 @@ fn from_str() {
     let string: &str = "fun";
     println!("{:?}", Token::token_from_str(string));
 }
+
+#[test]
+fn literals() {
+    assert_eq!(Token::from_string("foo_2"), Token::Ident("foo_2".into()));
+    assert_eq!(Token::from_string("int"), Token::Ident("int".into()));
+    assert_eq!(Token::from_string("42"), Token::IntLiteral(42, Type::None));
+    assert_eq!(Token::from_string("3.5"), Token::FloatLiteral(Float::new(3.5)));
+    assert_eq!(Token::from_string("'abc'"), Token::StringLiteral("abc".to_string()));
+    assert_eq!(Token::from_string("''"), Token::StringLiteral(String::new()));
+    assert_eq!(Token::from_string("'a'"), Token::CharLiteral('a'));
+    assert_eq!(Token::from_string("$"), Token::Other("$".to_string()));
+
+    assert_eq!(Token::FloatLiteral(Float::new(3.0)).to_string(), "3.0");
+    assert_eq!(Token::StringLiteral("abc".to_string()).to_string(), "'abc'");
+}
+
+#[test]
+fn numbers() {
+    let int = |value, type_| Ok(Token::IntLiteral(value, type_));
+    let float = |value| Ok(Token::FloatLiteral(Float::new(value)));
+
+    assert_eq!(Token::number_from_str("1_000_000"), int(1_000_000, Type::None));
+    assert_eq!(Token::number_from_str("0xFF"), int(255, Type::None));
+    assert_eq!(Token::number_from_str("0b1010"), int(10, Type::None));
+    assert_eq!(Token::number_from_str("0o17"), int(15, Type::None));
+    assert_eq!(Token::number_from_str("255byte"), int(255, Type::Byte));
+    assert_eq!(Token::number_from_str("0x1_int"), int(1, Type::Integer));
+    assert_eq!(Token::number_from_str("7bigint"), int(7, Type::BigInteger));
+    assert_eq!(Token::number_from_str("0xFbyte"), int(15, Type::Byte));
+
+    assert_eq!(Token::number_from_str("2.75"), float(2.75));
+    assert_eq!(Token::number_from_str("1e10"), float(1e10));
+    assert_eq!(Token::number_from_str("2.5E-3"), float(2.5e-3));
+    assert_eq!(Token::number_from_str("1_0.0_1"), float(10.01));
+
+    assert!(Token::number_from_str("0x").is_err());
+    assert!(Token::number_from_str("0b102").is_err());
+    assert!(Token::number_from_str("12abc").is_err());
+    assert!(Token::number_from_str("256byte").is_err());
+    assert!(Token::number_from_str("1.5int").is_err());
+    assert!(Token::number_from_str("1e").is_err());
+    assert!(Token::number_from_str("99999999999999999999").is_err());
+    assert!(Token::number_from_str("1e999").is_err());
+}
+
+#[test]
+fn escapes() {
+    assert_eq!(Token::unescape(r"a\n\t\\\'\0"), Ok("a\n\t\\'\0".to_string()));
+    assert_eq!(Token::unescape(r"\x41\u{e9}\u{1F600}"), Ok("Aé😀".to_string()));
+
+    assert!(Token::unescape(r"\q").is_err());
+    assert!(Token::unescape(r"\x8F").is_err());
+    assert!(Token::unescape(r"\x4").is_err());
+    assert!(Token::unescape(r"\u{110000}").is_err());
+    assert!(Token::unescape(r"\u{41").is_err());
+    assert!(Token::unescape("\\").is_err());
+
+    let content = "it's a\ttab\n";
+    assert_eq!(Token::unescape(&Token::escape(content)), Ok(content.to_string()));
+
+    assert_eq!(Token::from_string(r"'\n'"), Token::CharLiteral('\n'));
+    assert_eq!(Token::StringLiteral("a'b".to_string()).to_string(), r"'a\'b'");
+}
+
+#[test]
+fn metadata() {
+    assert_eq!(Token::keywords().count(), KEYWORDS.len());
+    assert!(Token::keywords().all(|keyword| keyword.is_keyword()));
+    assert!(Token::keywords().any(|keyword| keyword == Token::Static));
+    assert!(Token::keywords().any(|keyword| keyword == Token::Assembly));
+
+    // Only the keywords are classed as keywords
+    for string in KEYWORDS.iter().chain(OPERATORS) {
+        let token = Token::from_string(string);
+        let is_listed = Token::keywords().any(|keyword| keyword == token);
+        assert_eq!(token.is_keyword(), is_listed, "{}", string);
+    }
+
+    assert_eq!(Token::Function.category(), TokenCategory::Keyword);
+    assert_eq!(Token::ShiftLeft.category(), TokenCategory::Operator);
+    assert_eq!(Token::Comma.category(), TokenCategory::Delimiter);
+    assert_eq!(Token::Ident("a".into()).category(), TokenCategory::Identifier);
+    assert_eq!(Token::CharLiteral('a').category(), TokenCategory::Literal);
+    assert_eq!(Token::DocComment("a".to_string()).category(), TokenCategory::Trivia);
+
+    // Every operator of the tokenizer has a category
+    for operator in OPERATORS {
+        let category = Token::from_string(operator).category();
+        assert_ne!(category, TokenCategory::Other, "{}", operator);
+        assert_ne!(category, TokenCategory::Keyword, "{}", operator);
+    }
+
+    assert!(Token::Multiply.precedence() > Token::Plus.precedence());
+    assert!(Token::Plus.precedence() > Token::LessThan.precedence());
+    assert!(Token::And.precedence() > Token::Or.precedence());
+    assert_eq!(Token::Point.precedence(), None);
+    assert_eq!(Token::Minus.associativity(), Some(Associativity::Left));
+    assert_eq!(Token::PlusAssign.associativity(), Some(Associativity::Right));
+    assert_eq!(Token::Not.associativity(), None);
+    assert!(Token::Not.is_prefix_operator());
+    assert!(Token::Minus.is_prefix_operator() && Token::Minus.is_binary_operator());
+    assert!(!Token::Plus.is_prefix_operator());
+}
@@ -0,0 +1,155 @@
+// This file is part of "jup"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use crate::lang::tokens::{Token, TokenCategory, KEYWORDS, OPERATORS};
+
+/// Keywords of the version 0.1
+const KEYWORDS_V0_1: &[&str] = &[
+    "else", "if", "fun", "loop", "break", "continue", "ret", "static", "let",
+    "@",
+];
+
+/// Operators of the version 0.1, without the compound assignments and the
+/// logical and bitwise operators
+const OPERATORS_V0_1: &[&str] = &[
+    "=", "{", "}", ",", "//", "/", "==", "<", "<=", "-", ">", ">=", "*", "(",
+    ")", ".", "+", ";", "[", "]", ":",
+];
+
+/// Version of the Junon language, the keywords and the grammar rules change
+/// between the versions
+///
+/// The latest version is used by default, older source code is tokenized and
+/// parsed by giving its version to `Tokenizer` and `Parser`
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum LanguageVersion {
+    /// Language as tokenized and parsed by jup before the versions were
+    /// added, with one line assembly code only
+    V0_1,
+    /// Adds assembly blocks "@{ ... }", compound assignments like "+=" and the
+    /// operators "&&", "||", "!", "!=", "<<", ">>", "->" and "::"
+    #[default]
+    V0_2,
+}
+
+impl LanguageVersion {
+    pub const LATEST: Self = Self::V0_2;
+
+    /// Keywords as written in the source code
+    pub fn keywords(&self) -> &'static [&'static str] {
+        match *self {
+            Self::V0_1 => KEYWORDS_V0_1,
+            Self::V0_2 => KEYWORDS,
+        }
+    }
+
+    /// Operators and punctuation tokens as written in the source code
+    pub fn operators(&self) -> &'static [&'static str] {
+        match *self {
+            Self::V0_1 => OPERATORS_V0_1,
+            Self::V0_2 => OPERATORS,
+        }
+    }
+
+    /// Whether the keyword or the operator exists in this version \
+    /// Always `true` for the other strings, like identifiers
+    pub fn has_str(&self, string: &str) -> bool {
+        if KEYWORDS.contains(&string) {
+            self.keywords().contains(&string)
+        } else if OPERATORS.contains(&string) {
+            self.operators().contains(&string)
+        } else {
+            true
+        }
+    }
+
+    /// Whether the token exists in this version, see `has_str()`
+    pub fn has_token(&self, token: &Token) -> bool {
+        match token.category() {
+            TokenCategory::Keyword 
+                | TokenCategory::Operator 
+                | TokenCategory::Delimiter => self.has_str(&token.to_string()),
+            _ => true,
+        }
+    }
+
+    /// Assembly code can be written on several lines into "@{ ... }"
+    pub fn has_assembly_blocks(&self) -> bool {
+        *self >= Self::V0_2
+    }
+}
+
+#[test]
+fn tokenize_versions() {
+    use crate::lang::elements::type_::Type;
+    use crate::tokenizer::Tokenizer;
+
+    let source_code = "a += 1\n@{ nop\nlet a = b != c";
+
+    let tokens = |version: LanguageVersion| -> Vec<Token> {
+        let mut tokenizer = Tokenizer::from_source_code(source_code);
+        tokenizer.set_version(version);
+        tokenizer.run();
+
+        tokenizer.tokenized()
+            .iter()
+            .map(|token| token.token().clone())
+            .collect()
+    };
+
+    assert_eq!(&tokens(LanguageVersion::V0_1)[..6], &[
+        Token::Ident("a".into()),
+        Token::Plus,
+        Token::Assign,
+        Token::IntLiteral(1, Type::None),
+        Token::NewLine,
+        Token::Assembly,
+    ]);
+    assert_eq!(tokens(LanguageVersion::V0_1)[6], Token::AssemblyCode("{ nop".to_string()));
+
+    assert_eq!(&tokens(LanguageVersion::V0_2)[..3], &[
+        Token::Ident("a".into()),
+        Token::PlusAssign,
+        Token::IntLiteral(1, Type::None),
+    ]);
+    assert_eq!(tokens(LanguageVersion::V0_2)[5], Token::BracketOpen);
+
+    let mut tokenizer = Tokenizer::from_source_code(source_code);
+    tokenizer.set_version(LanguageVersion::V0_1);
+    tokenizer.run();
+    assert_eq!(tokenizer.errors()[0].message(), "Invalid character '!'");
+}
+
+#[test]
+fn parse_versions() {
+    use crate::lang::elements::Element;
+    use crate::lang::rules::Rule;
+    use crate::lang::span::Span;
+    use crate::lang::tokens::SpannedToken;
+    use crate::parser::Parser;
+
+    let tokens: Vec<SpannedToken> = [
+        Token::Ident("a".into()), 
+        Token::PlusAssign, 
+        Token::Ident("b".into()), 
+        Token::NewLine,
+    ]
+        .into_iter()
+        .map(|token| SpannedToken::new(token, Span::default()))
+        .collect();
+
+    let mut parser = Parser::new(tokens.clone());
+    parser.set_version(LanguageVersion::V0_1);
+    parser.run();
+    assert!(!parser.parsed().iter().any(|element| matches!(element, Element::Operation(_))));
+
+    let mut parser = Parser::new(tokens);
+    parser.run();
+    assert!(matches!(parser.parsed()[0], Element::Operation(_)));
+
+    assert!(Rule::from_token_in(Token::PlusAssign, LanguageVersion::V0_1).is_empty());
+    assert!(!Rule::from_token_in(Token::PlusAssign, LanguageVersion::V0_2).is_empty());
+    assert_eq!(Rule::from_token_in(Token::Assembly, LanguageVersion::V0_1).len(), 2);
+    assert_eq!(Rule::from_token(Token::Assembly).len(), 3);
+}
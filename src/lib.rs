@@ -3,7 +3,10 @@
 // Copyright (c) Junon, Antonin Hérault
 
 pub mod checking;
+pub mod diagnostic;
 pub mod lang;
 
+pub mod detokenizer;
+pub mod lowering;
 pub mod parser;
 pub mod tokenizer;
@@ -0,0 +1,359 @@
+// This file is part of "jup"
+// Under the MIT License
+// Copyright (c) Junon, Antonin Hérault
+
+use crate::diagnostic::Diagnostic;
+use crate::lang::ast::{
+    Block, Expression, FunctionDef, If, Item, Let, Module, Statement,
+};
+use crate::lang::elements::{
+    Element, 
+    condition::Condition, 
+    variable::Variable,
+};
+use crate::lang::span::Span;
+use crate::lang::tokens::Token;
+
+/// Builds the typed syntax tree from the elements given by `Parser`, with the
+/// errors found like a function declared into a block
+///
+/// See `Parser::parse_module()`
+pub fn lower(elements: Vec<Element>, docs: Vec<String>) -> (Module, Vec<Diagnostic>) {
+    let mut lowering = Lowering { errors: vec![] };
+    let items = lowering.items(elements);
+
+    (Module::new(docs, items), lowering.errors)
+}
+
+struct Lowering {
+    errors: Vec<Diagnostic>,
+}
+
+impl Lowering {
+    fn error(&mut self, message: String, span: Span) {
+        self.errors.push(Diagnostic::new(message, span));
+    }
+
+    fn items(&mut self, elements: Vec<Element>) -> Vec<Item> {
+        let mut items: Vec<Item> = vec![];
+
+        for element in elements {
+            let item = match element {
+                Element::Function(mut function) => {
+                    let body = function.take_body().map(|body| self.block(body));
+                    Item::Function(FunctionDef::new(function, body))
+                }
+                Element::Variable(variable) if variable.is_static() => {
+                    Item::Static(self.let_(variable))
+                }
+                element => match self.statement(element) {
+                    Some(statement) => Item::Statement(statement),
+                    None => continue,
+                },
+            };
+            items.push(item);
+        }
+
+        items
+    }
+
+    /// Block of an `Element::Expression`
+    fn block(&mut self, element: Element) -> Block {
+        let (elements, span) = match element {
+            Element::Expression(elements, span) => (elements, span),
+            element => {
+                let span = element.span();
+                (vec![element], span)
+            }
+        };
+
+        let statements = elements
+            .into_iter()
+            .filter_map(|element| self.statement(element))
+            .collect();
+
+        Block::new(statements, span)
+    }
+
+    /// Returns `None` for the new lines
+    fn statement(&mut self, element: Element) -> Option<Statement> {
+        let statement = match element {
+            Element::Other(Token::NewLine, _) => return None,
+            Element::Variable(variable) => Statement::Let(self.let_(variable)),
+            Element::Return(value, span) => {
+                Statement::Return(value.map(|value| self.expression(*value)), span)
+            }
+            Element::Condition(condition) => Statement::If(self.if_(condition)),
+            Element::Loop(block, span) => Statement::Loop(self.block(*block), span),
+            Element::Other(Token::LoopBreak, span) => Statement::Break(span),
+            Element::Other(Token::LoopContinue, span) => Statement::Continue(span),
+            Element::Assembly(assembly) => Statement::Assembly(assembly),
+            Element::Error(span) => Statement::Error(span),
+            Element::Function(function) => {
+                self.error(
+                    format!("Function '{}' declared into a block", function.id()),
+                    function.span()
+                );
+                Statement::Error(function.span())
+            }
+            element => Statement::Expression(self.expression(element)),
+        };
+
+        Some(statement)
+    }
+
+    fn let_(&mut self, mut variable: Variable) -> Let {
+        let value = variable.take_value().map(|value| self.expression(value));
+        Let::new(variable, value)
+    }
+
+    fn if_(&mut self, condition: Condition) -> If {
+        let (condition, then, else_, span) = condition.into_parts();
+
+        let else_ = else_.map(|else_| match else_ {
+            // "else if" is a block with only this "if"
+            Element::Condition(else_if) => {
+                let if_ = self.if_(else_if);
+                let span = if_.span();
+                Block::new(vec![Statement::If(if_)], span)
+            }
+            block => self.block(block),
+        });
+
+        If::new(self.expression(condition), self.block(then), else_, span)
+    }
+
+    fn expression(&mut self, element: Element) -> Expression {
+        match element {
+            Element::Other(token, span) if token.is_value() => Expression::Value(token, span),
+            Element::Operation(operation) => {
+                let (operator, arg1, arg2, span) = operation.into_parts();
+                let arg1 = self.expression(arg1);
+                let arg2 = self.expression(arg2);
+
+                Expression::Operation(operator, Box::new(arg1), Box::new(arg2), span)
+            }
+            Element::UnaryOperation(operation) => {
+                let (operator, arg, span) = operation.into_parts();
+                let arg = self.expression(arg);
+
+                Expression::UnaryOperation(operator, Box::new(arg), span)
+            }
+            Element::Call(call) => {
+                let (id, args, span) = call.into_parts();
+                let args = args
+                    .into_iter()
+                    .map(|arg| self.expression(arg))
+                    .collect();
+                Expression::Call(id.to_string(), args, span)
+            }
+            Element::Array(values, span) => {
+                let values = values
+                    .into_iter()
+                    .map(|value| self.expression(value))
+                    .collect();
+                Expression::Array(values, span)
+            }
+            element @ Element::Expression(..) => Expression::Block(self.block(element)),
+            // Parentheses around an expression
+            Element::Parameters(mut elements, _) if elements.len() == 1 => {
+                self.expression(elements.remove(0))
+            }
+            // Missing operand, reported by the parser
+            Element::Error(span) | Element::Other(Token::None, span) => Expression::Error(span),
+            Element::Other(token, span) => {
+                self.error(format!("Unexpected '{}'", token.to_string()), span);
+                Expression::Error(span)
+            }
+            element => {
+                let span = element.span();
+                self.error("Expected an expression".to_string(), span);
+                Expression::Error(span)
+            }
+        }
+    }
+}
+
+#[test]
+fn module() {
+    use std::path::Path;
+    use crate::parser::Parser;
+    use crate::tokenizer::Tokenizer;
+
+    let mut tokenizer = Tokenizer::from_path(Path::new("tests/parser.ju")).unwrap();
+    tokenizer.run();
+
+    let (module, errors) = Parser::new(tokenizer.tokenized().clone()).parse_module();
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    let functions: Vec<&FunctionDef> = module.items()
+        .iter()
+        .map(|item| match item {
+            Item::Function(function) => function,
+            item => panic!("expected a function, found {:?}", item),
+        })
+        .collect();
+    
+    let names: Vec<String> = functions.iter().map(|function| function.signature().id()).collect();
+    assert_eq!(names, vec!["main", "foo", "foo_2"]);
+
+    // Only given as a block
+    assert!(functions.iter().all(|function| function.signature().body().is_none()));
+
+    let main = functions[0].body().unwrap().statements();
+    let kinds: Vec<&str> = main
+        .iter()
+        .map(|statement| match statement {
+            Statement::Let(let_) => match let_.value() {
+                Some(Expression::Value(..)) => "let value",
+                Some(Expression::Block(_)) => "let block",
+                Some(Expression::Array(..)) => "let array",
+                None => "let",
+                Some(_) => "let other",
+            },
+            Statement::Expression(Expression::Operation(..)) => "operation",
+            Statement::Expression(Expression::Call(..)) => "call",
+            Statement::If(if_) => {
+                assert!(matches!(if_.condition(), Expression::Operation(Token::Equal, ..)));
+                assert_eq!(if_.then().statements().len(), 1);
+                assert_eq!(if_.else_().unwrap().statements().len(), 1);
+                "if"
+            }
+            Statement::Return(None, _) => "ret",
+            statement => panic!("unexpected statement {:?}", statement),
+        })
+        .collect();
+
+    assert_eq!(kinds, vec![
+        "let value", "let", "operation", "let block", "operation", "let array", 
+        "operation", "call", "if", "ret",
+    ]);
+
+    match functions[2].body().unwrap().statements()[..] {
+        [Statement::Return(Some(Expression::Value(Token::IntLiteral(5, _), _)), _)] => {}
+        ref statements => panic!("unexpected statements {:?}", statements),
+    }
+}
+
+#[test]
+fn statements() {
+    use crate::parser::Parser;
+    use crate::tokenizer::Tokenizer;
+
+    let source_code = "//! Module\n".to_owned()
+        + "static a: int = 1 + 2\n"
+        + "fun foo {\n"
+        + "    loop {\n"
+        + "        if a < 2 {\n"
+        + "            break\n"
+        + "        } else if a {\n"
+        + "            continue\n"
+        + "        } else {\n"
+        + "            ret a *\n"
+        + "        }\n"
+        + "    }\n"
+        + "    if a\n"
+        + "    else\n"
+        + "}\n";
+
+    let mut tokenizer = Tokenizer::from_source_code(&source_code);
+    tokenizer.run();
+
+    let (module, errors) = Parser::new(tokenizer.tokenized().clone()).parse_module();
+
+    let errors: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+    assert_eq!(errors, vec![
+        "10:19: Missing operand for '*'",
+        "13:8: Missing block after 'if'",
+        "14:5: Unexpected 'else'",
+    ]);
+
+    assert_eq!(module.docs(), &vec!["Module".to_string()]);
+
+    match &module.items()[0] {
+        Item::Static(static_) => {
+            assert!(static_.variable().is_static());
+            assert_eq!(&source_code[static_.span().range()], "static a: int = 1 + 2");
+        }
+        item => panic!("expected a static, found {:?}", item),
+    }
+
+    let function = match &module.items()[1] {
+        Item::Function(function) => function,
+        item => panic!("expected a function, found {:?}", item),
+    };
+    let loop_ = match &function.body().unwrap().statements()[0] {
+        Statement::Loop(block, _) => block,
+        statement => panic!("expected a loop, found {:?}", statement),
+    };
+    let if_ = match &loop_.statements()[0] {
+        Statement::If(if_) => if_,
+        statement => panic!("expected an if, found {:?}", statement),
+    };
+    assert!(matches!(if_.then().statements()[..], [Statement::Break(_)]));
+
+    let else_if = match &if_.else_().unwrap().statements()[..] {
+        [Statement::If(else_if)] => else_if,
+        statements => panic!("expected an if, found {:?}", statements),
+    };
+    assert!(matches!(else_if.condition(), Expression::Value(..)));
+    assert!(matches!(else_if.then().statements()[..], [Statement::Continue(_)]));
+    assert!(matches!(
+        else_if.else_().unwrap().statements()[..],
+        [Statement::Return(Some(Expression::Operation(..)), _)]
+    ));
+}
+
+#[test]
+fn values() {
+    use crate::parser::Parser;
+    use crate::tokenizer::Tokenizer;
+
+    let source_code = "fun foo {\n".to_owned()
+        + "    let a: int[2] = [x + 1, 2]\n"
+        + "    let b: int[3] = [\n"
+        + "        foo(a),\n"
+        + "        { 3 },\n"
+        + "    ]\n"
+        + "    ret -a * 2 + 1\n"
+        + "}\n";
+
+    let mut tokenizer = Tokenizer::from_source_code(&source_code);
+    tokenizer.run();
+
+    let (module, errors) = Parser::new(tokenizer.tokenized().clone()).parse_module();
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    let statements = match &module.items()[..] {
+        [Item::Function(function)] => function.body().unwrap().statements(),
+        items => panic!("expected a function, found {:?}", items),
+    };
+
+    let values: Vec<Vec<&str>> = statements[..2]
+        .iter()
+        .map(|statement| match statement {
+            Statement::Let(let_) => match let_.value() {
+                Some(Expression::Array(values, _)) => values
+                    .iter()
+                    .map(|value| &source_code[value.span().range()])
+                    .collect(),
+                value => panic!("expected an array, found {:?}", value),
+            },
+            statement => panic!("expected a let, found {:?}", statement),
+        })
+        .collect();
+
+    assert_eq!(values, vec![vec!["x + 1", "2"], vec!["foo(a)", "{ 3 }"]]);
+
+    match &statements[2] {
+        Statement::Return(Some(value @ Expression::Operation(Token::Plus, arg1, ..)), _) => {
+            assert!(matches!(
+                **arg1, 
+                Expression::Operation(Token::Multiply, ref negative, ..)
+                    if matches!(**negative, Expression::UnaryOperation(Token::Minus, ..))
+            ));
+            assert_eq!(&source_code[value.span().range()], "-a * 2 + 1");
+        }
+        statement => panic!("expected a return, found {:?}", statement),
+    }
+}
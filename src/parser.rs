@@ -2,42 +2,88 @@
 // Under the MIT License
 // Copyright (c) Junon, Antonin Hérault
 
-use crate::lang::tokens::Token;
+use std::rc::Rc;
+
+use crate::diagnostic::Diagnostic;
+use crate::lang::ast::Module;
+use crate::lang::span::Span;
+use crate::lang::tokens::{Associativity, SpannedToken, Token};
+use crate::lang::version::LanguageVersion;
+use crate::lowering::lower;
 use crate::lang::elements::{
     Element, 
-    function::Function, 
-    operation::Operation,
+    assembly::{Assembly, AssemblyLine, Binding, BindingKind},
+    call::Call,
+    condition::Condition,
+    function::{Function, Parameter},
+    operation::{Operation, UnaryOperation},
     type_::Type,
     variable::Variable
 };
 
 /// Transforms tokens to a collection of `Element` to be easily used by the 
 /// compiler
+///
+/// The parser never panics : malformed constructs are reported as errors, 
+/// see `errors()`
 pub struct Parser {
-    tokenized: Vec<Token>,
+    /// Shared with the sub parsers, which only parse a part of it
+    tokenized: Rc<[SpannedToken]>,
+    /// Index following the last token to parse
+    end: usize,
     parsed: Vec<Element>,
     n_token: usize,
     asked_for_push: Option<Element>,
+    /// Errors found while parsing, like a missing name after "fun"
+    errors: Vec<Diagnostic>,
+    /// Version of the language, giving the grammar rules
+    version: LanguageVersion,
+
+    /// Lines of the "///" comments waiting for the element they document
+    docs: Vec<String>,
+    /// Lines of the "//!" comments, documenting the whole module
+    module_docs: Vec<String>,
 }
 
 impl Parser {
     /// Takes a tokenized file thanks to `Tokenizer` to parse these tokens
-    pub fn new(tokenized: Vec<Token>) -> Self {
+    pub fn new(tokenized: Vec<SpannedToken>) -> Self {
+        let end = tokenized.len();
+        Self::with_range(tokenized.into(), 0, end, LanguageVersion::default())
+    }
+
+    /// Parser of the tokens from `start` to `end` (excluded), for the sub 
+    /// parts like expressions
+    fn with_range(
+        tokenized: Rc<[SpannedToken]>,
+        start: usize,
+        end: usize,
+        version: LanguageVersion
+    ) -> Self {
         Parser {
             tokenized,
+            end,
             parsed: vec![],
-            n_token: 0,
+            n_token: start,
             asked_for_push: None,
+            errors: vec![],
+            version,
+
+            docs: vec![],
+            module_docs: vec![],
         }
     }
 
-    pub fn run(&mut self) {
-        for (i, token) in self.tokenized.clone().iter().enumerate() {
-            if i != self.n_token {
-                continue;
-            }
+    /// Tokens of the elements missing from this version are given as 
+    /// `Element::Other`, the latest version by default
+    ///
+    /// Should be the same version as the one given to `Tokenizer`
+    pub fn set_version(&mut self, version: LanguageVersion) {
+        self.version = version;
+    }
 
-            self.n_token = i;
+    pub fn run(&mut self) {
+        while self.n_token < self.end {
             let elements = self.check();
             
             for element in elements {
@@ -46,39 +92,175 @@ impl Parser {
         }
     }
 
+    /// Runs the parser and gives the elements with the errors found, in the
+    /// same order as in the source code
+    ///
+    /// The elements are always given, even when errors are found
+    pub fn parse(mut self) -> (Vec<Element>, Vec<Diagnostic>) {
+        self.run();
+        (self.parsed, self.errors)
+    }
+
+    /// Runs the parser and gives the typed syntax tree of the source code, 
+    /// with the errors found in the same order as in the source code
+    ///
+    /// Easier to use than the elements : a function comes with its body, an
+    /// "if" with its condition and its blocks
+    pub fn parse_module(mut self) -> (Module, Vec<Diagnostic>) {
+        self.run();
+
+        let (module, errors) = lower(self.parsed, self.module_docs);
+        self.errors.extend(errors);
+        self.errors.sort_by_key(|error| error.span().start());
+
+        (module, self.errors)
+    }
+
+    /// Parses the tokens from the current one to `end` (excluded), the errors
+    /// are given to this parser
+    fn run_sub_parser(&mut self, end: usize) -> Vec<Element> {
+        let mut parser = Self::with_range(
+            Rc::clone(&self.tokenized), self.n_token, end, self.version
+        );
+        parser.run();
+
+        self.errors.extend(parser.errors);
+        parser.parsed
+    }
+
+    /// Tokens to parse
+    fn tokens(&self) -> &[SpannedToken] {
+        &self.tokenized[..self.end]
+    }
+
+    /// Whether the current token is `token`, `false` after the last token
+    fn is_next(&self, token: Token) -> bool {
+        self.tokens().get(self.n_token).is_some_and(|next| *next == token)
+    }
+
+    /// Location of the token at `i`, or of the last token when there is no
+    /// more token
+    fn span_at(&self, i: usize) -> Span {
+        match self.tokens().get(i).or(self.tokens().last()) {
+            Some(token) => token.span(),
+            None => Span::default(),
+        }
+    }
+
+    fn error(&mut self, message: String, span: Span) {
+        self.errors.push(Diagnostic::new(message, span));
+    }
+
     fn check(&mut self) -> Vec<Element> {
         self.n_token += 1;
 
-        match &self.tokenized[self.n_token -1] {
+        match self.tokens()[self.n_token -1].token() {
+            Token::DocComment(text) => {
+                self.docs.push(text.clone());
+                return vec![];
+            }
+            Token::ModuleDocComment(text) => {
+                self.module_docs.push(text.clone());
+                return vec![];
+            }
+            // Documentation comments are given to these elements
+            Token::Function | Token::Variable | Token::Static | Token::NewLine => {}
+            // Nothing to document
+            _ => self.docs.clear(),
+        }
+
+        match self.tokens()[self.n_token -1].token() {
             Token::SquareBracketOpen => self.when_array(),
             Token::Assembly => self.when_assembly(),
             Token::BracketOpen => self.when_expression(),
             Token::Function => self.when_function(),
             Token::ParenOpen => self.when_parameters(),
-            Token::Variable => self.when_variable(),
+            Token::Static | Token::Variable => self.when_variable(),
+            Token::Ident(_) if self.is_call() => self.when_call(),
             Token::Return => self.when_return(),
-            Token::Plus | Token::Minus | Token::Multiply | Token::Divide 
-                | Token::Assign | Token::Equal | Token::LessThan 
-                | Token::LessThanOrEqual | Token::MoreThan 
-                | Token::MoreThanOrEqual => self.when_operation(),
-            token => vec![Element::Other(token.clone())],
+            Token::ConditionIf => self.when_condition(),
+            Token::Loop => self.when_loop(),
+            token if (token.is_binary_operator() || token.is_prefix_operator()) 
+                && self.version.has_token(token) => 
+            {
+                self.when_operation()
+            }
+            // Not closing anything, or not following an "if"
+            token @ (Token::BracketClose 
+                | Token::ParenClose 
+                | Token::SquareBracketClose 
+                | Token::ConditionElse) => 
+            {
+                let span = self.span_at(self.n_token - 1);
+                self.error(format!("Unexpected '{}'", token.to_string()), span);
+                vec![Element::Error(span)]
+            }
+            token => vec![
+                Element::Other(token.clone(), self.tokens()[self.n_token -1].span())
+            ],
+        }
+    }
+
+    /// Runs `when` for the current token, its elements are replaced by 
+    /// `Element::Error` when an error is found in them
+    ///
+    /// The following tokens are skipped until the next line, block or 
+    /// declaration, so the parsing goes on after the malformed part
+    fn recovering(&mut self, when: fn(&mut Self) -> Vec<Element>) -> Vec<Element> {
+        let first = self.n_token - 1;
+        let errors_count = self.errors.len();
+
+        let elements = when(self);
+        if self.errors.len() == errors_count {
+            return elements;
         }
+
+        self.synchronize();
+        vec![Element::Error(self.span_from(first))]
     }
 
-    /// Retrieves all tokens into `start_token` and `end_token` and skips sub 
-    /// parts
-    fn retrieve_token_into(&mut self, start_token: Token, end_token: Token) -> Vec<Token> {
-        // Retrieves tokens into
-        let mut tokens_into = self.tokenized[self.n_token..].to_vec();
+    /// Skips the tokens until one from where the parsing can go on
+    fn synchronize(&mut self) {
+        while let Some(token) = self.tokens().get(self.n_token) {
+            match token.token() {
+                Token::NewLine
+                    | Token::BracketOpen
+                    | Token::BracketClose
+                    | Token::Function
+                    | Token::Variable
+                    | Token::Static => break,
+                _ => self.n_token += 1,
+            }
+        }
+    }
 
+    /// Whether the identifier just consumed is called, followed by 
+    /// `Token::ParenOpen`
+    fn is_call(&self) -> bool {
+        self.is_next(Token::ParenOpen)
+    }
+
+    /// Location covered by the tokens from the `first` one to the last consumed
+    /// token
+    fn span_from(&self, first: usize) -> Span {
+        let last = (self.n_token.max(first + 1) - 1).min(self.end - 1);
+        self.tokens()[first].span().to(&self.tokens()[last].span())
+    }
+
+    /// Retrieves the index of the `end_token` closing the tokens into 
+    /// `start_token` and `end_token` and skips sub parts
+    ///
+    /// When `end_token` is missing, it's reported and the index following the 
+    /// last token is returned
+    fn retrieve_token_into(&mut self, start_token: Token, end_token: Token) -> usize {
         // Skips sub parts into to avoid finishing before it's really finished
-        let mut i_end = 0;
+        let mut i_end = self.n_token;
         let mut is_sub_part = 0;
 
-        for token in tokens_into.iter() {
-            if token == &start_token {
+        for token in self.tokens().iter().skip(self.n_token) {
+            if *token == start_token {
                 is_sub_part += 1;
-            } else if token == &end_token {
+            } else if *token == end_token {
                 if is_sub_part == 0 {
                     break;
                 }
@@ -88,192 +270,737 @@ impl Parser {
             i_end += 1;
         }
 
-        tokens_into[..i_end].to_vec()
+        if i_end >= self.end {
+            let span = self.span_at(self.n_token - 1);
+            self.error(format!("Unclosed '{}'", start_token.to_string()), span);
+        }
+        i_end
     }
 
-    /// Creates one `Element::Array` object with all the array's values tokens
+    /// Creates one `Element::Array` object with the parsed values
     fn when_array(&mut self) -> Vec<Element> {
-        let array_tokens = self.retrieve_token_into(
+        let first = self.n_token - 1;
+        let end = self.retrieve_token_into(
             Token::SquareBracketOpen, Token::SquareBracketClose
         );
 
-        self.n_token += array_tokens.len() + 1;
-
-        // Creates the array's values object
-        let mut values: Vec<Token> = vec![];
+        let values: Vec<Element> = self.run_sub_parser(end)
+            .into_iter()
+            .filter(|value| !matches!(value, Element::Other(Token::Comma | Token::NewLine, _)))
+            .collect();
+        self.n_token = end + 1;
         
-        for token in array_tokens {
-            if token == Token::Comma {
-                continue;
+        vec![Element::Array(values, self.span_from(first))]
+    }
+
+    /// Creates one `Element::Assembly` object from an assembly line or an
+    /// assembly block with its bindings
+    fn when_assembly(&mut self) -> Vec<Element> {
+        let first = self.n_token - 1;
+        let mut lines: Vec<AssemblyLine> = vec![];
+        let mut bindings: Vec<Binding> = vec![];
+
+        if self.is_next(Token::BracketOpen) {
+            self.n_token += 1;
+
+            // Lines of the block, until `Token::BracketClose`
+            while let Some(token) = self.tokens().get(self.n_token) {
+                match token.token() {
+                    Token::AssemblyCode(code) => {
+                        lines.push(AssemblyLine::new(code.clone(), token.span()));
+                    }
+                    Token::NewLine => {}
+                    _ => break,
+                }
+                self.n_token += 1;
+            }
+
+            if self.is_next(Token::BracketClose) {
+                self.n_token += 1;
+                bindings = self.retrieve_bindings();
+            }
+        } else if let Some(token) = self.tokens().get(self.n_token) {
+            if let Token::AssemblyCode(code) = token.token() {
+                lines.push(AssemblyLine::new(code.clone(), token.span()));
+                self.n_token += 1;
             }
-            values.push(token.clone());
         }
-        
-        vec![Element::Array(values)]
+
+        vec![Element::Assembly(Assembly::new(lines, bindings, self.span_from(first)))]
     }
 
-    fn when_assembly(&mut self) -> Vec<Element> {
-        let code: Token = self.tokenized[self.n_token].clone();
-        self.n_token += 1;
-        vec![Element::Assembly(code)]
+    /// Bindings written after an assembly block, like "in(rdi) a, out(rax) b"
+    ///
+    /// A malformed binding is reported and the rest of the line is skipped
+    fn retrieve_bindings(&mut self) -> Vec<Binding> {
+        let mut bindings: Vec<Binding> = vec![];
+
+        if self.is_next(Token::NewLine) || self.n_token >= self.end {
+            return bindings;
+        }
+
+        while let Some(binding) = self.retrieve_binding() {
+            bindings.push(binding);
+
+            if self.is_next(Token::Comma) {
+                self.n_token += 1;
+            } else if self.is_next(Token::NewLine) || self.n_token >= self.end {
+                return bindings;
+            } else {
+                break;
+            }
+        }
+
+        // Until the end of the line
+        let first = self.n_token;
+        while self.n_token < self.end && !self.is_next(Token::NewLine) {
+            self.n_token += 1;
+        }
+
+        let span = match self.n_token > first {
+            true => self.span_from(first),
+            false => self.span_at(first - 1),
+        };
+        self.error("Malformed assembly binding".to_string(), span);
+
+        bindings
+    }
+
+    /// Binding made of its kind, "(", the register, ")" and the variable
+    fn retrieve_binding(&mut self) -> Option<Binding> {
+        let tokens = self.tokens().get(self.n_token..self.n_token + 5)?;
+
+        let binding = match (tokens[0].token(), tokens[2].token(), tokens[4].token()) {
+            (Token::Ident(kind), Token::Ident(register), Token::Ident(_)) 
+                if tokens[1] == Token::ParenOpen && tokens[3] == Token::ParenClose => 
+            {
+                Binding::new(
+                    BindingKind::from_string(kind)?,
+                    register.to_string(),
+                    tokens[4].token().clone(),
+                    tokens[0].span().to(&tokens[4].span())
+                )
+            }
+            _ => return None,
+        };
+
+        self.n_token += 5;
+        Some(binding)
     }
 
     /// Creates one `Element::Expression` object with a parsed the parsed 
     /// elements retrieved into the expression
     fn when_expression(&mut self) -> Vec<Element> {
-        let expr_tokens = self.retrieve_token_into(
+        let first = self.n_token - 1;
+        let end = self.retrieve_token_into(
             Token::BracketOpen, Token::BracketClose
         );
         
         // Parse these tokens
-        let mut expr_parser = Self::new(expr_tokens.clone());
-        expr_parser.run();
+        let elements = self.run_sub_parser(end);
         
-        self.n_token += expr_tokens.len() + 1;
+        self.n_token = end + 1;
 
-        vec![Element::Expression(expr_parser.parsed().clone())]
+        vec![Element::Expression(elements, self.span_from(first))]
     }
 
-    /// Creates one `Element::Function` object
+    /// Creates one `Element::Function` object with its body
+    ///
+    /// Malformed parameters and return type are reported, the function keeps
+    /// the valid parameters. Without a name, the function and its body are one
+    /// `Element::Error`
     fn when_function(&mut self) -> Vec<Element> {
-        let id = self.retrieve_id();
+        let first = self.n_token - 1;
+        let is_named = self.tokens()
+            .get(self.n_token)
+            .is_some_and(|id| matches!(*id.token(), Token::Ident(_)));
+
+        let mut function = self.when_function_signature();
+        if !is_named {
+            self.synchronize();
+        }
+
+        if self.is_next(Token::BracketOpen) {
+            self.n_token += 1;
+            function.set_body(self.when_expression().remove(0));
+        }
+
+        match is_named {
+            true => vec![Element::Function(function)],
+            false => vec![Element::Error(self.span_from(first))],
+        }
+    }
+
+    /// Creates one `Function` object without its body
+    fn when_function_signature(&mut self) -> Function {
+        let first = self.n_token - 1;
+        let id = self.retrieve_id("fun");
         
         // Retrieves parameters when exist
-        let params = if self.tokenized[self.n_token] == Token::ParenOpen {
+        let params = if self.is_next(Token::ParenOpen) {
             self.n_token += 1;
-            self.when_parameters()[0].clone()
+            self.retrieve_parameters()
         } else {
-            Element::Parameters(vec![])
+            vec![]
         };
 
         let return_type = self.retrieve_type_token();
         
-        vec![Element::Function(Function::new(id, params, return_type))]
+        let mut function = Function::new(id, params, return_type, self.span_from(first));
+        function.set_docs(std::mem::take(&mut self.docs));
+        
+        function
     }
 
-    fn when_operation(&mut self) -> Vec<Element> {   
-        self.parsed.pop(); // arg1 has to be only integrated in the operation 
+    /// Creates one `Element::Call` object with the parsed arguments
+    fn when_call(&mut self) -> Vec<Element> {
+        let first = self.n_token - 1;
+        let id = self.tokens()[first].token().clone();
 
-        let mut ret_elements: Vec<Element> = vec![];
+        self.n_token += 1; // skip Token::ParenOpen
+        let end = self.retrieve_token_into(
+            Token::ParenOpen, Token::ParenClose
+        );
 
-        let operation = Element::Operation(Operation::new(
-            // Operator
-            self.tokenized[self.n_token - 1].clone(),
-            // Argument 1
-            self.tokenized[self.n_token - 2].clone(),        
-            // Argument 2
-            {
-                let arg2 = self.tokenized[self.n_token].clone();
-                match arg2 {
-                    Token::BracketOpen => {
-                        self.n_token += 1;
-                        ret_elements.extend(self.when_expression());
-                    }
-                    Token::SquareBracketOpen => {
-                        self.n_token += 1;
-                        ret_elements.extend(self.when_array());
-                    }
-                    _ => {}
-                }
-                arg2
+        let args: Vec<Element> = self.run_sub_parser(end)
+            .into_iter()
+            .filter(|arg| !matches!(arg, Element::Other(Token::Comma | Token::NewLine, _)))
+            .collect();
+        self.n_token = end + 1;
+
+        vec![Element::Call(Call::new(id, args, self.span_from(first)))]
+    }
+
+    /// Creates one `Element::Operation` object from the previous element and
+    /// all the operators and operands following it, or from a prefix operator
+    /// when there is no previous operand
+    ///
+    /// The operators are nested following their precedence and associativity,
+    /// parentheses group an operation as one operand
+    fn when_operation(&mut self) -> Vec<Element> {
+        let operator = self.tokens()[self.n_token - 1].token();
+
+        // Not following an operand, like "-1" at the beginning of a line
+        let is_prefix = operator.is_prefix_operator()
+            && (!operator.is_binary_operator() || !self.parsed.last().is_some_and(is_operand));
+
+        if is_prefix {
+            self.n_token -= 1;
+
+            let (arg1, span) = self.parse_operand();
+            let (operation, _) = self.parse_operation(arg1, span, 0);
+            return vec![operation];
+        }
+
+        let (mut arg1, span) = match self.parsed.pop() {
+            Some(element) if is_operand(&element) => {
+                let span = element.span();
+                (element, span)
             }
-        ));
+            element => {
+                // Not an operand, like a new line before "* 2"
+                self.parsed.extend(element);
 
+                let span = self.tokens()[self.n_token - 1].span();
+                let operator = self.tokens()[self.n_token - 1].token().to_string();
+                self.error(format!("Missing operand for '{}'", operator), span);
+                (Element::Other(Token::None, span), span)
+            }
+        };
+
+        // Without the parentheses around it
+        while let Element::Parameters(mut elements, _) = arg1 {
+            arg1 = elements.remove(0);
+        }
+
+        // Back to the operator
+        self.n_token -= 1;
+
+        let (operation, _) = self.parse_operation(arg1, span, 0);
+        vec![operation]
+    }
+
+    /// Parses the operators binding at least as tight as `min_precedence` 
+    /// and their operands, following `arg1` written at `span`
+    ///
+    /// Precedence climbing : the right operand of an operator takes all the
+    /// following operators binding tighter than it
+    fn parse_operation(
+        &mut self,
+        mut arg1: Element,
+        mut span: Span,
+        min_precedence: u8
+    ) -> (Element, Span) {
+        while let Some(operator) = self.tokens().get(self.n_token) {
+            let operator = operator.token().clone();
+
+            let precedence = match operator.precedence() {
+                Some(precedence) 
+                    if precedence >= min_precedence && self.version.has_token(&operator) => 
+                {
+                    precedence
+                }
+                _ => break,
+            };
+            self.n_token += 1;
+
+            // Operators of the same precedence are grouped on the left, 
+            // except the right associative ones
+            let next_precedence = match operator.associativity() {
+                Some(Associativity::Right) => precedence,
+                _ => precedence + 1,
+            };
+
+            let (arg2, arg2_span) = self.parse_operand();
+            let (arg2, arg2_span) = self.parse_operation(arg2, arg2_span, next_precedence);
+
+            span = span.to(&arg2_span);
+            arg1 = Element::Operation(Operation::new(operator, arg1, arg2, span));
+        }
+
+        (arg1, span)
+    }
+
+    /// Parses the operand following an operator with its prefix operators, 
+    /// with its location including the parentheses around it
+    fn parse_operand(&mut self) -> (Element, Span) {
+        let first = self.n_token;
+
+        let token = match self.tokens().get(first) {
+            Some(token) => token.token().clone(),
+            None => return self.missing_operand(first),
+        };
         self.n_token += 1;
-        ret_elements.insert(0, operation);
-        ret_elements
+
+        let operand = match token {
+            Token::ParenOpen => {
+                let end = self.retrieve_token_into(Token::ParenOpen, Token::ParenClose);
+
+                let (operand, operand_span) = self.parse_operand();
+                let (operand, _) = self.parse_operation(operand, operand_span, 0);
+
+                // Only one operand can be grouped, like "(1 2)"
+                if self.n_token < end {
+                    let span = self.span_at(self.n_token).to(&self.span_at(end - 1));
+                    let token = self.tokens()[self.n_token].token().to_string();
+                    self.error(format!("Unexpected '{}' in the parentheses", token), span);
+                }
+
+                self.n_token = end + 1;
+                operand
+            }
+            Token::BracketOpen => self.when_expression().remove(0),
+            Token::SquareBracketOpen => self.when_array().remove(0),
+            Token::Ident(_) if self.is_call() => self.when_call().remove(0),
+            token if token.is_value() => Element::Other(token, self.span_from(first)),
+            token if token.is_prefix_operator() && self.version.has_token(&token) => {
+                let precedence = token.prefix_precedence().unwrap_or_default();
+
+                // Only the operators binding tighter than it are in its operand
+                let (arg, arg_span) = self.parse_operand();
+                let (arg, _) = self.parse_operation(arg, arg_span, precedence);
+
+                Element::UnaryOperation(UnaryOperation::new(token, arg, self.span_from(first)))
+            }
+            _ => {
+                self.n_token -= 1;
+                return self.missing_operand(first);
+            }
+        };
+
+        (operand, self.span_from(first))
+    }
+
+    /// Reports the operand missing at `first`, after the operator written
+    /// before it
+    ///
+    /// The operand is `Element::Other` with `Token::None` at the operator's 
+    /// location
+    fn missing_operand(&mut self, first: usize) -> (Element, Span) {
+        // There is no operator before a prefix operator written first
+        let span = self.span_at(first.saturating_sub(1));
+        let operator = match first.checked_sub(1).and_then(|i| self.tokens().get(i)) {
+            Some(operator) => operator.token().to_string(),
+            None => String::new(),
+        };
+
+        self.error(format!("Missing operand for '{}'", operator), span);
+        (Element::Other(Token::None, span), span)
     }
 
     fn when_return(&mut self) -> Vec<Element> {
+        let first = self.n_token - 1;
+        let value = self.retrieve_value_or_expr().map(Box::new);
         vec![
-            Element::Return(self.retrieve_value_or_expr())
+            Element::Return(value, self.span_from(first))
         ]
     }
 
+    /// Creates one `Element::Condition` object with the condition, its block
+    /// and the else block
+    ///
+    /// Without condition or block, the whole "if" is an `Element::Error`
+    fn when_condition(&mut self) -> Vec<Element> {
+        let first = self.n_token - 1;
+
+        let condition = match self.is_next(Token::BracketOpen) {
+            true => None,
+            false => self.retrieve_value_or_expr(),
+        };
+        let condition = match condition {
+            Some(condition) => condition,
+            None => {
+                let span = self.span_at(first);
+                self.error("Missing condition after 'if'".to_string(), span);
+
+                // The block is a part of the malformed "if"
+                if self.is_next(Token::BracketOpen) {
+                    self.retrieve_block("if");
+                }
+                return self.skip_malformed(first);
+            }
+        };
+
+        let then = match self.retrieve_block("if") {
+            Some(then) => then,
+            None => return self.skip_malformed(first),
+        };
+
+        let else_ = if self.is_next(Token::ConditionElse) {
+            self.n_token += 1;
+
+            if self.is_next(Token::ConditionIf) {
+                // "else if" is an else block with only this "if"
+                self.n_token += 1;
+                self.when_condition().pop()
+            } else {
+                match self.retrieve_block("else") {
+                    Some(block) => Some(block),
+                    None => return self.skip_malformed(first),
+                }
+            }
+        } else {
+            None
+        };
+
+        let condition = Condition::new(condition, then, else_, self.span_from(first));
+        vec![Element::Condition(condition)]
+    }
+
+    /// Creates one `Element::Loop` object with its block
+    fn when_loop(&mut self) -> Vec<Element> {
+        let first = self.n_token - 1;
+
+        match self.retrieve_block("loop") {
+            Some(block) => vec![Element::Loop(Box::new(block), self.span_from(first))],
+            None => self.skip_malformed(first),
+        }
+    }
+
+    /// Parses the block "{ ... }" written after `keyword`, its absence is 
+    /// reported
+    fn retrieve_block(&mut self, keyword: &str) -> Option<Element> {
+        if !self.is_next(Token::BracketOpen) {
+            let span = self.span_at(self.n_token - 1);
+            self.error(format!("Missing block after '{}'", keyword), span);
+            return None;
+        }
+        self.n_token += 1;
+
+        self.when_expression().pop()
+    }
+
+    /// Skips the tokens of a malformed element started at `first`, see
+    /// `recovering()`
+    fn skip_malformed(&mut self, first: usize) -> Vec<Element> {
+        self.synchronize();
+        vec![Element::Error(self.span_from(first))]
+    }
+
     /// Always return a vector of exactly one element
     fn when_parameters(&mut self) -> Vec<Element> {
-        let params_tokens = self.retrieve_token_into(
+        let first = self.n_token - 1;
+        let end = self.retrieve_token_into(
             Token::ParenOpen, Token::ParenClose
         );
-        self.n_token += params_tokens.len() + 1;
 
-        let parsed_params: Vec<Element> = {
-            let mut parser = Parser::new(params_tokens);
-            parser.run();
-            parser.parsed().to_vec()
-        };
+        let parsed_params = self.run_sub_parser(end);
+        self.n_token = end + 1;
 
         vec![
-            Element::Parameters(parsed_params)
+            Element::Parameters(parsed_params, self.span_from(first))
         ]
     }
 
+    /// Creates one `Element::Variable` object with its value, for "let" and
+    /// "static"
     fn when_variable(&mut self) -> Vec<Element> {
-        vec![
-            Element::Variable(Variable::new(
-                self.retrieve_id(),
-                self.retrieve_type_token(),
-                if self.tokenized[self.n_token] == Token::Assign {
-                    self.n_token += 1;
-                    self.retrieve_value_or_expr()
-                } else {
-                    Token::None
-                }            
-            ))
-        ]
+        let mut elements = self.recovering(Self::when_variable_declaration);
+
+        if let Some(Element::Variable(variable)) = elements.first_mut() {
+            if self.is_next(Token::Assign) {
+                self.n_token += 1;
+                // Always found, checked with the declaration
+                if let Some(value) = self.retrieve_value_or_expr() {
+                    variable.set_value(value);
+                }
+            }
+        }
+        elements
     }
 
-    fn retrieve_id(&mut self) -> Token {
-        self.n_token += 1; // skip id
-        self.tokenized[self.n_token -1].clone()
+    /// Creates one `Element::Variable` object without its value
+    fn when_variable_declaration(&mut self) -> Vec<Element> {
+        let first = self.n_token - 1;
+        let keyword = self.tokens()[first].token().clone();
+        let id = self.retrieve_id(&keyword.to_string());
+        let type_ = self.retrieve_type_token();
+
+        let has_value = self.tokens()
+            .get(self.n_token + 1)
+            .is_some_and(|value| starts_value(value.token()));
+
+        if self.is_next(Token::Assign) && !has_value {
+            let span = self.span_at(self.n_token);
+            self.error("Missing value after '='".to_string(), span);
+        }
+
+        let mut variable = Variable::new(id, type_, self.span_from(first));
+        variable.set_static(keyword == Token::Static);
+        variable.set_docs(std::mem::take(&mut self.docs));
+
+        vec![Element::Variable(variable)]
     }
 
-    fn retrieve_type_token(&mut self) -> Type {
-        // When the type is explicitly written
-        if self.tokenized[self.n_token] == Token::TypeDef {
-            self.n_token += 2; // skip Token::TypeDef and type
-            
-            let type_token = self.tokenized[self.n_token -1].clone();
-            
-            // Array type found
-            if self.tokenized[self.n_token] == Token::SquareBracketOpen {
-                // Token::SquareBracketOpen, ::Other and ::SquareBracketClose
-                self.n_token += 3;
-
-                let array_size = self.tokenized[self.n_token -2]
-                    .clone()
-                    .to_string()
-                    .parse::<usize>()
-                    .unwrap();
-                
-                Type::array_from_string(type_token.to_string(), array_size)
+    /// Retrieves the name written after `keyword`, `Token::None` when it's
+    /// missing
+    fn retrieve_id(&mut self, keyword: &str) -> Token {
+        match self.tokens().get(self.n_token) {
+            Some(token) if matches!(*token.token(), Token::Ident(_)) => {
+                let id = token.token().clone();
+                self.n_token += 1; // skip id
+                id
+            }
+            _ => {
+                let span = self.span_at(self.n_token - 1);
+                self.error(format!("Missing name after '{}'", keyword), span);
+                Token::None
+            }
+        }
+    }
+
+    /// Retrieves the parameters written into parentheses, like 
+    /// "(a: int, b: byte)"
+    ///
+    /// Missing types, duplicate names and anything else than a comma after a 
+    /// parameter are reported
+    fn retrieve_parameters(&mut self) -> Vec<Parameter> {
+        let end = self.retrieve_token_into(
+            Token::ParenOpen, Token::ParenClose
+        );
+        let mut params: Vec<Parameter> = vec![];
+
+        loop {
+            self.skip_new_lines();
+            if self.n_token >= end {
+                break;
+            }
+
+            let first = self.n_token;
+            let name = self.tokens()[first].clone();
+
+            if matches!(*name.token(), Token::Ident(_)) {
+                self.n_token += 1;
+                if let Some(param) = self.retrieve_parameter(name, first, &params) {
+                    params.push(param);
+                }
+
+                // Only a comma can follow
+                self.skip_new_lines();
+                if self.n_token < end && !self.is_next(Token::Comma) {
+                    self.unexpected_in_parameters(end);
+                }
             } else {
-                Type::from_string(type_token.to_string())
+                self.unexpected_in_parameters(end);
+            }
+
+            if self.n_token < end {
+                self.n_token += 1; // skip Token::Comma
             }
+        }
+
+        self.n_token = end + 1;
+        params
+    }
+
+    /// Retrieves the type of the parameter named by the token at `first`,
+    /// the other `params` are already retrieved
+    ///
+    /// Returns `None` when the parameter is not valid : without type or with
+    /// the name of another one
+    fn retrieve_parameter(
+        &mut self, 
+        name: SpannedToken,
+        first: usize,
+        params: &[Parameter]
+    ) -> Option<Parameter> {
+        let name_string = name.token().to_string();
+        let errors_count = self.errors.len();
+
+        let type_ = if self.is_next(Token::TypeDef) {
+            self.retrieve_type_token()
         } else {
+            self.error(
+                format!("Missing type for the parameter '{}'", name_string), 
+                name.span()
+            );
             Type::None
+        };
+
+        if params.iter().any(|param| param.name() == name_string) {
+            self.error(format!("Duplicate parameter '{}'", name_string), name.span());
         }
+
+        if self.errors.len() > errors_count {
+            return None;
+        }
+        Some(Parameter::new(name.token().clone(), type_, self.span_from(first)))
     }
 
-    /// `Token::BracketOpen` is returned when it's an expression, 
-    /// `Token::Other(...)` is returned when it's a value. But when 
-    /// `Token::None` is returned it's because there is no value or expr
-    fn retrieve_value_or_expr(&mut self) -> Token {
-        let next = self.tokenized[self.n_token].clone();
+    /// Reports the current token and skips the tokens until the next comma,
+    /// or until the parameters' `end`
+    fn unexpected_in_parameters(&mut self, end: usize) {
+        let token = self.tokens()[self.n_token].clone();
+        self.error(
+            format!("Unexpected '{}' in the parameters", token.token().to_string()),
+            token.span()
+        );
 
-        match next {
-            Token::BracketOpen | Token::SquareBracketOpen => next,
-            Token::Other(_) => {
-                self.n_token += 1;
-                next
+        while self.n_token < end && !self.is_next(Token::Comma) {
+            self.n_token += 1;
+        }
+    }
+
+    fn skip_new_lines(&mut self) {
+        while self.is_next(Token::NewLine) {
+            self.n_token += 1;
+        }
+    }
+
+    /// Retrieves the type written after `Token::TypeDef`, `Type::None` when 
+    /// there is no type or when it's invalid
+    fn retrieve_type_token(&mut self) -> Type {
+        // When the type is explicitly written
+        if !self.is_next(Token::TypeDef) {
+            return Type::None;
+        }
+        self.n_token += 1;
+
+        let type_token = match self.tokens().get(self.n_token) {
+            Some(token) if matches!(*token.token(), Token::Ident(_)) => token.clone(),
+            _ => {
+                let span = self.span_at(self.n_token - 1);
+                self.error("Missing type after ':'".to_string(), span);
+                return Type::None;
+            }
+        };
+        self.n_token += 1;
+
+        let type_ = match Type::from_string(type_token.token().to_string()) {
+            Some(type_) => type_,
+            None => {
+                self.error(
+                    format!("Unknown type '{}'", type_token.token().to_string()), 
+                    type_token.span()
+                );
+                Type::None
+            }
+        };
+
+        // Array type found
+        if !self.is_next(Token::SquareBracketOpen) {
+            return type_;
+        }
+        self.n_token += 1;
+
+        // Token::IntLiteral and Token::SquareBracketClose
+        let size = self.tokens().get(self.n_token).map(|token| token.token().clone());
+
+        match size {
+            Some(Token::IntLiteral(size, _)) if self.tokens().get(self.n_token + 1)
+                .is_some_and(|token| *token == Token::SquareBracketClose) => 
+            {
+                self.n_token += 2;
+                Type::new_array(type_, size as usize)
+            }
+            _ => {
+                let span = self.span_at(self.n_token);
+                let size = size.map(|size| size.to_string()).unwrap_or_default();
+                self.error(format!("Invalid array size '{}'", size), span);
+
+                let end = self.retrieve_token_into(
+                    Token::SquareBracketOpen, Token::SquareBracketClose
+                );
+                self.n_token = end + 1;
+                type_
             }
-            _ => Token::None,
         }
     }
 
+    /// Parses the value written from the current token with the operations 
+    /// on it, like "1 + 2", "{ ... }" or "[1, 2]"
+    ///
+    /// Returns `None` when there is no value
+    fn retrieve_value_or_expr(&mut self) -> Option<Element> {
+        if !self.tokens().get(self.n_token).is_some_and(|next| starts_value(next.token())) {
+            return None;
+        }
+
+        let (value, span) = self.parse_operand();
+        let (value, _) = self.parse_operation(value, span, 0);
+        Some(value)
+    }
+
     pub fn parsed(&self) -> &Vec<Element> {
         &self.parsed
     }
+
+    /// Returns the errors found while parsing, including the ones of the 
+    /// expressions and parameters
+    pub fn errors(&self) -> &Vec<Diagnostic> {
+        &self.errors
+    }
+
+    /// Lines of the "//!" comments found in the parsed tokens
+    pub fn module_docs(&self) -> &Vec<String> {
+        &self.module_docs
+    }
+}
+
+/// Whether a value, an expression "{ ... }" or an array can start with this
+/// token
+fn starts_value(token: &Token) -> bool {
+    token.is_value() 
+        || token.is_prefix_operator()
+        || matches!(
+            *token,
+            Token::BracketOpen | Token::SquareBracketOpen | Token::ParenOpen
+        )
+}
+
+/// Whether the parsed element can be an operand of an operation
+fn is_operand(element: &Element) -> bool {
+    match element {
+        Element::Other(token, _) => token.is_value(),
+        Element::Array(..)
+            | Element::Call(_)
+            | Element::Expression(..)
+            | Element::Operation(_)
+            | Element::UnaryOperation(_) => true,
+        // Parentheses around an operand
+        Element::Parameters(elements, _) => elements.len() == 1 && is_operand(&elements[0]),
+        _ => false,
+    }
 }
 
 #[test]
@@ -309,3 +1036,531 @@ pub fn operations() {
 
     println!("{:#?}", parser.parsed());
 }
+
+#[test]
+pub fn operations_precedence() {
+    use crate::tokenizer::Tokenizer;
+
+    /// Operation written with parentheses around each operation
+    fn written(element: &Element) -> String {
+        match element {
+            Element::Other(token, _) => token.to_string(),
+            Element::Operation(operation) => format!(
+                "({} {} {})",
+                written(operation.arg1()),
+                operation.operator().to_string(),
+                written(operation.arg2())
+            ),
+            Element::UnaryOperation(operation) => format!(
+                "({}{})",
+                operation.operator().to_string(),
+                written(operation.arg())
+            ),
+            Element::Call(call) => format!(
+                "{}({})",
+                call.id(),
+                call.args().iter().map(written).collect::<Vec<String>>().join(", ")
+            ),
+            Element::Expression(elements, _) => format!(
+                "{{ {} }}",
+                elements.iter().map(written).collect::<Vec<String>>().join(" ")
+            ),
+            Element::Array(values, _) => format!(
+                "[{}]",
+                values.iter().map(written).collect::<Vec<String>>().join(", ")
+            ),
+            element => panic!("unexpected operand {:?}", element),
+        }
+    }
+
+    let source_code = "{ 1 + 2 * 3 }\n".to_owned()
+        + "a = b = c - d - e\n"
+        + "(1 + 2) * 3 <= x / (y - 1)\n"
+        + "a == b < c << 1\n"
+        + "a = { 5 + 2 } * foo(b, { c }, 1 + 2) - [1, 2]\n"
+        + "a = -1\n"
+        + "-a * b + !c == !(d - -1)\n"
+        + "x = foo(-1, [1, -2]) - -(2 + x)\n"
+        + "let y: int = -1 * 2\n";
+
+    let mut tokenizer = Tokenizer::from_source_code(&source_code);
+    tokenizer.run();
+
+    let mut parser = Parser::new(tokenizer.tokenized().clone());
+    parser.run();
+
+    let operations: Vec<&Element> = parser.parsed()
+        .iter()
+        .flat_map(|element| match element {
+            Element::Expression(elements, _) => elements.iter().collect(),
+            element => vec![element],
+        })
+        .filter(|element| matches!(element, Element::Operation(_)))
+        .collect();
+
+    let value = parser.parsed()
+        .iter()
+        .find_map(|element| match element {
+            Element::Variable(variable) => variable.value(),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(written(value), "((-1) * 2)");
+    assert!(parser.errors().is_empty(), "{:?}", parser.errors());
+
+    let written: Vec<String> = operations.iter().map(|operation| written(operation)).collect();
+
+    assert_eq!(written, vec![
+        "(1 + (2 * 3))",
+        "(a = (b = ((c - d) - e)))",
+        "(((1 + 2) * 3) <= (x / (y - 1)))",
+        "(a == (b < (c << 1)))",
+        "(a = (({ (5 + 2) } * foo(b, { c }, (1 + 2))) - [1, 2]))",
+        "(a = (-1))",
+        "((((-a) * b) + (!c)) == (!(d - (-1))))",
+        "(x = (foo((-1), [1, (-2)]) - (-(2 + x))))",
+    ]);
+
+    assert_eq!(&source_code[operations[2].span().range()], "(1 + 2) * 3 <= x / (y - 1)");
+}
+
+#[test]
+pub fn errors() {
+    use crate::tokenizer::Tokenizer;
+
+    let source_code = "fun (a: int) {\n".to_owned()
+        + "    let b: foo = 1\n"
+        + "    let c: int[x] = [1]\n"
+        + "    let d: int =\n"
+        + "    foo(1, { 2 + 3 }\n"
+        + "}\n"
+        + "let e: int = 1 +\n"
+        + "* 2\n"
+        + "a = (1 2 3) + 3\n"
+        + "let";
+
+    let mut tokenizer = Tokenizer::from_source_code(&source_code);
+    tokenizer.run();
+
+    let (_, errors) = Parser::new(tokenizer.tokenized().clone()).parse();
+
+    let errors: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+    assert_eq!(errors, vec![
+        "1:1: Missing name after 'fun'",
+        "2:12: Unknown type 'foo'",
+        "3:16: Invalid array size 'x'",
+        "4:16: Missing value after '='",
+        "5:8: Unclosed '('",
+        "7:16: Missing operand for '+'",
+        "8:1: Missing operand for '*'",
+        "9:8: Unexpected '2' in the parentheses",
+        "10:1: Missing name after 'let'",
+    ]);
+}
+
+#[test]
+pub fn recovery() {
+    use crate::tokenizer::Tokenizer;
+
+    let source_code = "fun (a: int) {\n".to_owned()
+        + "    let b: int[x] = [1, 2] + c\n"
+        + "    let d: int = 4\n"
+        + "    )\n"
+        + "}\n"
+        + "static e: foo let f: int = 5\n"
+        + "fun g {}\n";
+
+    let mut tokenizer = Tokenizer::from_source_code(&source_code);
+    tokenizer.run();
+
+    let (elements, errors) = Parser::new(tokenizer.tokenized().clone()).parse();
+    assert_eq!(errors.len(), 4);
+
+    let written: Vec<&str> = elements
+        .iter()
+        .filter(|element| !matches!(element, Element::Other(Token::NewLine, _)))
+        .map(|element| &source_code[element.span().range()])
+        .collect();
+    assert_eq!(written, vec![
+        "fun (a: int) {\n    let b: int[x] = [1, 2] + c\n    let d: int = 4\n    )\n}",
+        "static e: foo",
+        "let f: int = 5",
+        "fun g {}",
+    ]);
+
+    // Without name, the function and its body are one error
+    assert!(matches!(elements[0], Element::Error(_)));
+    assert!(matches!(elements[2], Element::Error(_)));
+    assert!(matches!(elements[3], Element::Variable(_)));
+    match &elements[5] {
+        Element::Function(function) => assert!(function.body().is_some()),
+        element => panic!("expected a function, found {:?}", element),
+    }
+}
+
+#[test]
+pub fn never_panics() {
+    use crate::tokenizer::Tokenizer;
+
+    let mut sources = vec![
+        std::fs::read_to_string("tests/test1.ju").unwrap(),
+        std::fs::read_to_string("tests/parser.ju").unwrap(),
+        std::fs::read_to_string("tests/operations.ju").unwrap(),
+        "fun foo(a: int[, b: byte[5): int[] { ret ( }".to_string(),
+        "let a: int[5 = @{ in(rax) static b: ] ) + * ,".to_string(),
+        "fun foo(1, a b: int,, ret: byte[2] c\n: int".to_string(),
+        // Prefix operators written first
+        "-1".to_string(),
+        "!a".to_string(),
+    ];
+    // Each construct accepted at the beginning of an element, written first 
+    // and followed by a malformed part
+    sources.extend([
+        "/// doc\nfun", "//! doc\nlet", "[1, -, ]", "@{ nop } in(", "@ nop", 
+        "{ ret + }", "fun a(b: c) -", "(1 2", "static a: = ", "let a = * 2", 
+        "foo(1, ", "ret -", "if { else", "if a {} else if", "loop loop {", 
+        "+ 1", "* = 2", "== !", "} ) ] else", "a b 'c",
+    ].map(String::from));
+    // All the beginnings of the source code, like while it's being written
+    for source_code in sources.clone() {
+        let ends = source_code.char_indices().map(|(i, _)| i);
+        sources.extend(ends.map(|end| source_code[..end].to_string()));
+    }
+
+    for source_code in sources {
+        let mut tokenizer = Tokenizer::from_source_code(&source_code);
+        tokenizer.run();
+
+        let tokens = tokenizer.tokenized().clone();
+        // Each token can be the last one
+        for end in 0..=tokens.len() {
+            Parser::new(tokens[..end].to_vec()).parse();
+        }
+    }
+}
+
+#[test]
+pub fn parameters() {
+    use crate::tokenizer::Tokenizer;
+
+    let source_code = "fun foo(a: int, b: byte[2],\n    c: bigint) {}\n".to_owned()
+        + "fun bar(a: int, b, a: byte, 5, c: int d) {}\n"
+        + "fun baz {}\n";
+
+    let mut tokenizer = Tokenizer::from_source_code(&source_code);
+    tokenizer.run();
+
+    let mut parser = Parser::new(tokenizer.tokenized().clone());
+    parser.run();
+
+    let errors: Vec<String> = parser.errors().iter().map(|error| error.to_string()).collect();
+    assert_eq!(errors, vec![
+        "3:17: Missing type for the parameter 'b'",
+        "3:20: Duplicate parameter 'a'",
+        "3:29: Unexpected '5' in the parameters",
+        "3:39: Unexpected 'd' in the parameters",
+    ]);
+
+    let params: Vec<(String, String, &str)> = match &parser.parsed()[0] {
+        Element::Function(function) => function.params()
+            .iter()
+            .map(|param| (param.name(), param.type_().to_string(), &source_code[param.span().range()]))
+            .collect(),
+        element => panic!("expected a function, found {:?}", element),
+    };
+    assert_eq!(params, vec![
+        ("a".to_string(), "int".to_string(), "a: int"),
+        ("b".to_string(), "byte[2]".to_string(), "b: byte[2]"),
+        ("c".to_string(), "bigint".to_string(), "c: bigint"),
+    ]);
+
+    // Only the valid parameters, the function keeps its body
+    match &parser.parsed()[2] {
+        Element::Function(function) => {
+            let params: Vec<String> = function.params().iter().map(|param| param.name()).collect();
+            assert_eq!(params, vec!["a", "c"]);
+            assert!(function.body().is_some());
+        }
+        element => panic!("expected a function, found {:?}", element),
+    }
+
+    match &parser.parsed()[4] {
+        Element::Function(function) => assert!(function.params().is_empty()),
+        element => panic!("expected a function, found {:?}", element),
+    }
+}
+
+#[test]
+pub fn function_bodies() {
+    use std::path::Path;
+    use crate::tokenizer::Tokenizer;
+
+    let source_code = std::fs::read_to_string("tests/parser.ju").unwrap();
+
+    let mut tokenizer = Tokenizer::from_path(Path::new("tests/parser.ju")).unwrap();
+    tokenizer.run();
+
+    let mut parser = Parser::new(tokenizer.tokenized().clone());
+    parser.run();
+
+    let functions: Vec<&Function> = parser.parsed()
+        .iter()
+        .filter_map(|element| match element {
+            Element::Function(function) => Some(function),
+            _ => None,
+        })
+        .collect();
+    
+    // Only the functions, the bodies are not given as elements
+    let others = parser.parsed()
+        .iter()
+        .filter(|element| !matches!(element, Element::Other(Token::NewLine, _)))
+        .count();
+    assert_eq!(others, functions.len());
+
+    let bodies: Vec<(String, &str)> = functions
+        .iter()
+        .map(|function| {
+            let body = function.body().expect("no body");
+            (function.id(), &source_code[body.span().range()])
+        })
+        .collect();
+
+    assert_eq!(bodies[1], ("foo".to_string(), "{\n    ret [1, 2, 3, 4, 5]\n}"));
+    assert_eq!(bodies[2], ("foo_2".to_string(), "{\n    ret 5\n}"));
+
+    match functions[2].body() {
+        Some(Element::Expression(elements, _)) => {
+            let value = match &elements[1] {
+                Element::Return(Some(value), _) => value,
+                element => panic!("expected a return, found {:?}", element),
+            };
+            assert!(matches!(**value, Element::Other(Token::IntLiteral(5, Type::None), _)));
+            assert_eq!(&source_code[value.span().range()], "5");
+        }
+        body => panic!("expected an expression, found {:?}", body),
+    }
+    assert_eq!(&source_code[functions[2].span().range()], "fun foo_2(): int {\n    ret 5\n}");
+}
+
+#[test]
+pub fn spans() {
+    use crate::tokenizer::Tokenizer;
+
+    let source_code = "fun foo(a: int): int {\n    let b: int = 5\n}\n";
+
+    let mut tokenizer = Tokenizer::from_source_code(source_code);
+    tokenizer.run();
+
+    let mut parser = Parser::new(tokenizer.tokenized().clone());
+    parser.run();
+
+    let function = match &parser.parsed()[0] {
+        Element::Function(function) => function,
+        element => panic!("expected a function, found {:?}", element),
+    };
+    assert_eq!(&source_code[function.span().range()], "fun foo(a: int): int {\n    let b: int = 5\n}");
+
+    match function.body() {
+        Some(Element::Expression(elements, span)) => {
+            assert_eq!(&source_code[span.range()], "{\n    let b: int = 5\n}");
+            assert_eq!(&source_code[elements[1].span().range()], "let b: int = 5");
+            assert_eq!(elements[1].span().line(), 2);
+            assert_eq!(elements[1].span().column(), 5);
+        }
+        body => panic!("expected an expression, found {:?}", body),
+    }
+}
+
+#[test]
+pub fn doc_comments() {
+    use crate::tokenizer::Tokenizer;
+
+    let source_code = "//! The module\n".to_owned()
+        + "\n"
+        + "/// The function\n"
+        + "/// Second line\n"
+        + "fun foo {\n"
+        + "    //// Not a documentation\n"
+        + "    let a: int = 1\n"
+        + "}\n"
+        + "/// Forgotten\n"
+        + "ret\n"
+        + "///The static\n"
+        + "static b: int = 2\n";
+
+    let mut tokenizer = Tokenizer::from_source_code(&source_code);
+    tokenizer.run();
+
+    let mut parser = Parser::new(tokenizer.tokenized().clone());
+    parser.run();
+
+    assert_eq!(parser.module_docs(), &vec!["The module".to_string()]);
+
+    for element in parser.parsed() {
+        match element {
+            Element::Function(function) => {
+                assert_eq!(function.docs(), &vec![
+                    "The function".to_string(), 
+                    "Second line".to_string()
+                ]);
+
+                match function.body() {
+                    Some(Element::Expression(elements, _)) => match &elements[2] {
+                        Element::Variable(variable) => assert!(variable.docs().is_empty()),
+                        element => panic!("expected a variable, found {:?}", element),
+                    },
+                    body => panic!("expected an expression, found {:?}", body),
+                }
+            }
+            Element::Variable(variable) => {
+                assert!(variable.is_static());
+                assert_eq!(variable.docs(), &vec!["The static".to_string()]);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[test]
+pub fn assembly() {
+    use crate::tokenizer::Tokenizer;
+
+    let source_code = "@ syscall\n".to_owned()
+        + "@{\n"
+        + "    mov rax, rdi\n"
+        + "    add rax, 1\n"
+        + "} in(rdi) a, out(rax) b\n";
+
+    let mut tokenizer = Tokenizer::from_source_code(&source_code);
+    tokenizer.run();
+
+    let mut parser = Parser::new(tokenizer.tokenized().clone());
+    parser.run();
+
+    let assemblies: Vec<&Assembly> = parser.parsed()
+        .iter()
+        .filter_map(|element| match element {
+            Element::Assembly(assembly) => Some(assembly),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(assemblies.len(), 2);
+
+    let lines: Vec<&String> = assemblies[0].lines().iter().map(|line| line.code()).collect();
+    assert_eq!(lines, vec!["syscall"]);
+    assert!(assemblies[0].bindings().is_empty());
+
+    let block = assemblies[1];
+    let lines: Vec<&String> = block.lines().iter().map(|line| line.code()).collect();
+    assert_eq!(lines, vec!["mov rax, rdi", "add rax, 1"]);
+    assert_eq!(block.lines()[1].span().line(), 4);
+    assert_eq!(
+        &source_code[block.span().range()], 
+        "@{\n    mov rax, rdi\n    add rax, 1\n} in(rdi) a, out(rax) b"
+    );
+
+    let bindings = block.bindings();
+    assert_eq!(bindings.len(), 2);
+    assert_eq!(bindings[0].kind(), BindingKind::In);
+    assert_eq!(bindings[0].register(), "rdi");
+    assert_eq!(bindings[0].variable(), "a");
+    assert_eq!(bindings[1].kind(), BindingKind::Out);
+    assert_eq!(&source_code[bindings[1].span().range()], "out(rax) b");
+}
+
+#[test]
+pub fn malformed_bindings() {
+    use crate::tokenizer::Tokenizer;
+
+    let source_code = "@{\n    nop\n} in(rdi) 5\n".to_owned()
+        + "@{\n    nop\n} in(rdi) a, foo(rsi) b\n"
+        + "@{\n    nop\n} in(rdi) a,\n"
+        + "let c = 1\n";
+
+    let mut tokenizer = Tokenizer::from_source_code(&source_code);
+    tokenizer.run();
+
+    let (elements, errors) = Parser::new(tokenizer.tokenized().clone()).parse();
+
+    let errors: Vec<(String, &str)> = errors
+        .iter()
+        .map(|error| (error.to_string(), &source_code[error.span().range()]))
+        .collect();
+
+    assert_eq!(errors, vec![
+        ("3:3: Malformed assembly binding".to_string(), "in(rdi) 5"),
+        ("6:14: Malformed assembly binding".to_string(), "foo(rsi) b"),
+        ("9:12: Malformed assembly binding".to_string(), ","),
+    ]);
+
+    // The rest of the lines is not parsed as code
+    let elements: Vec<&Element> = elements
+        .iter()
+        .filter(|element| !matches!(element, Element::Other(Token::NewLine, _)))
+        .collect();
+
+    assert_eq!(elements.len(), 4);
+    assert!(matches!(elements[1], Element::Assembly(assembly) if assembly.bindings().len() == 1));
+    assert!(matches!(elements[3], Element::Variable(_)));
+}
+
+#[test]
+pub fn conditions() {
+    use crate::tokenizer::Tokenizer;
+
+    let source_code = "if a == 1 {\n".to_owned()
+        + "    b = 1\n"
+        + "} else if a {\n"
+        + "} else {\n"
+        + "    ret\n"
+        + "}\n"
+        + "loop {\n"
+        + "    break\n"
+        + "}\n"
+        + "if {\n"
+        + "}\n"
+        + "loop\n";
+
+    let mut tokenizer = Tokenizer::from_source_code(&source_code);
+    tokenizer.run();
+
+    let (elements, errors) = Parser::new(tokenizer.tokenized().clone()).parse();
+
+    let errors: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+    assert_eq!(errors, vec![
+        "10:1: Missing condition after 'if'",
+        "12:1: Missing block after 'loop'",
+    ]);
+
+    let written: Vec<&str> = elements
+        .iter()
+        .filter(|element| !matches!(element, Element::Other(Token::NewLine, _)))
+        .map(|element| &source_code[element.span().range()])
+        .collect();
+    assert_eq!(written, vec![
+        "if a == 1 {\n    b = 1\n} else if a {\n} else {\n    ret\n}",
+        "loop {\n    break\n}",
+        "if {\n}",
+        "loop",
+    ]);
+
+    let condition = match &elements[0] {
+        Element::Condition(condition) => condition,
+        element => panic!("expected a condition, found {:?}", element),
+    };
+    assert!(matches!(condition.condition(), Element::Operation(_)));
+    assert!(matches!(condition.then(), Element::Expression(..)));
+
+    match condition.else_() {
+        Some(Element::Condition(else_if)) => {
+            assert!(matches!(else_if.condition(), Element::Other(Token::Ident(_), _)));
+            assert!(matches!(else_if.else_(), Some(Element::Expression(..))));
+        }
+        else_ => panic!("expected an else if, found {:?}", else_),
+    }
+
+    assert!(matches!(elements[2], Element::Loop(..)));
+    assert!(matches!(elements[4], Element::Error(_)));
+    assert!(matches!(elements[6], Element::Error(_)));
+}
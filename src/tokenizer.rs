@@ -6,32 +6,90 @@
 //! They return a boolean value, if the returned value is `true`, instruction
 //! `continue` will be called in the run loop
 
+use std::collections::VecDeque;
 use std::fmt;
 use std::fs::File;
 use std::io;
-use std::io::Read;
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
 
-use crate::lang::tokens::Token;
+use unicode_xid::UnicodeXID;
+
+use crate::diagnostic::Diagnostic;
+use crate::lang::span::Span;
+use crate::lang::symbol::SymbolTable;
+use crate::lang::tokens::{SpannedToken, Token, Trivia};
+use crate::lang::version::LanguageVersion;
 
 /// A way to get a tokenized file content as tokens list \
 /// Could be called as `Tokenizer`
+///
+/// The source code is read and tokenized line by line, so the tokens can also
+/// be retrieved on demand as an `Iterator`
 pub struct Tokenizer {
+    /// Where the source code is read from
+    reader: Box<dyn BufRead>,
+    /// Current line of the source code
     content: String,
-    tokenized: Vec<Token>,
+    /// The next line is read here, to reuse the allocation of the previous 
+    /// lines
+    buffer: String,
+    /// Byte offset of the current line in the source code
+    content_start: usize,
+    /// All the source code was read
+    is_finished: bool,
+    tokenized: Vec<SpannedToken>,
+    /// Tokens waiting to be given by the iterator
+    queue: VecDeque<SpannedToken>,
+    /// Names of the identifiers, shared by the tokens
+    symbols: SymbolTable,
+    /// Errors found while tokenizing, like malformed numbers
+    errors: Vec<Diagnostic>,
+    /// Version of the language, giving the keywords and the operators
+    version: LanguageVersion,
+
+    /// Whitespaces and comments are kept as trivia of the tokens, with the
+    /// source code of each token
+    is_lossless: bool,
+    /// Trivia waiting for the next token
+    trivia: Vec<Trivia>,
 
     /// Current token as string
     token: String,
+    /// Location of the current token's first character
+    token_start: Span,
+
+    // Location of the current character
+    pos: usize,
+    line: usize,
+    column: usize,
+    /// Columns between two tab stops
+    tab_width: usize,
 
-    was_double_char: bool,
+    /// Remaining characters of the last operator, already pushed
+    to_skip: usize,
     /// The assembly line will be pushed as "this"
     is_asm_code: bool,
+    /// Opening location of the current assembly block "@{", its lines are
+    /// pushed as raw code until "}"
+    asm_block: Option<Span>,
     /// Comments are ignored in the tokenized vector
     is_comment: bool,
+    /// Opening locations of the block comments currently open, they can be
+    /// nested
+    block_comments: Vec<Span>,
+    /// The current character is the end of "/*" or "*/"
+    was_comment_delimiter: bool,
+    /// The current character is the end of "\r\n"
+    was_carriage_return: bool,
 
     // Variables for strings creation
     is_string: bool,
+    /// Raw content, escape sequences are decoded at the end of the string
     string_content: String,
+    string_start: Span,
+    /// The previous character was a backslash starting an escape sequence
+    is_escaped: bool,
 }
 
 impl fmt::Debug for Tokenizer {
@@ -43,7 +101,7 @@ impl fmt::Debug for Tokenizer {
                 writeln!(f, "{:?}", Token::NewLine)?;
                 continue;
             }
-            write!(f, "{:?} ", token)?;
+            write!(f, "{:?} ", token.token())?;
         }
 
         writeln!(f, "---- ")?;
@@ -62,52 +120,155 @@ impl Tokenizer {
     /// Note : The path is not checked, it should be valid before giving it as
     /// parameter to this associated function
     pub fn from_path(file_path: &Path) -> Result<Self, io::Error> {
-        let source_code = Self::read_file_content(file_path)?;
-        Ok(Self::from_source_code(&source_code))
+        Ok(Self::from_reader(File::open(file_path)?))
     }
 
     /// Creates a new `Tokenizer` object from the given source code content
     pub fn from_source_code(source_code: &str) -> Self {
+        Self::from_reader(io::Cursor::new(source_code.as_bytes().to_vec()))
+    }
+
+    /// Creates a new `Tokenizer` object reading the source code from `reader`,
+    /// only when the tokens are needed
+    ///
+    /// When the source code cannot be read (like invalid UTF-8), the error is
+    /// reported in `errors()` and the tokenizer stops there
+    pub fn from_reader<R: Read + 'static>(reader: R) -> Self {
         Self {
-            content: source_code.to_owned(),
+            reader: Box::new(BufReader::new(reader)),
+            content: String::new(),
+            buffer: String::new(),
+            content_start: 0,
+            is_finished: false,
             tokenized: vec![],
+            queue: VecDeque::new(),
+            symbols: SymbolTable::new(),
+            errors: vec![],
+            version: LanguageVersion::default(),
+
+            is_lossless: false,
+            trivia: vec![],
 
             token: String::new(),
+            token_start: Span::default(),
 
-            was_double_char: false,
+            pos: 0,
+            line: 1,
+            column: 1,
+            tab_width: 1,
+
+            to_skip: 0,
             // The assembly line will be pushed as "this"
             is_asm_code: false,
+            asm_block: None,
             // Comments are ignored in the tokenized vector
             is_comment: false,
+            block_comments: vec![],
+            was_comment_delimiter: false,
+            was_carriage_return: false,
 
             // Variables for strings creation
             is_string: false,
             string_content: String::new(),
+            string_start: Span::default(),
+            is_escaped: false,
         }
     }
 
+    /// In lossless mode, whitespaces and comments are not dropped but kept as
+    /// trivia of the next token, and each token keeps its source code
+    ///
+    /// Concatenating `SpannedToken::lossless_text()` of all the tokens gives 
+    /// back the exact source code
+    pub fn set_lossless(&mut self, is_lossless: bool) {
+        self.is_lossless = is_lossless;
+    }
+
+    /// A tab moves the column to the next tab stop, each `tab_width` columns,
+    /// like in a text editor
+    ///
+    /// By default, a tab is one column like the other characters
+    pub fn set_tab_width(&mut self, tab_width: usize) {
+        self.tab_width = tab_width.max(1);
+    }
+
+    /// Keywords and operators missing from this version are tokenized as
+    /// identifiers and invalid characters, the latest version by default
+    pub fn set_version(&mut self, version: LanguageVersion) {
+        self.version = version;
+    }
+
     /// Parsed content is not returned by this function but by `tokenized()`
     pub fn run(&mut self) {
-        // Replaces all the tabulations to space character, to simplify the
-        // operations and avoid creating matches for tabulations when spaces 
-        // matches already exist
-        self.content = self.content.replace("\t", " ");
-
-        for (i, c) in self.content.clone().chars().enumerate() {
-            // Comments will be everytime skipped
-            if c != '\n' && self.is_comment {
-                continue;
+        while self.tokenize_line() {}
+    }
+
+    /// Reads and tokenizes the next line of the source code, returns `false`
+    /// when all the source code was tokenized
+    fn tokenize_line(&mut self) -> bool {
+        if self.is_finished {
+            return false;
+        }
+
+        self.buffer.clear();
+
+        match self.reader.read_line(&mut self.buffer) {
+            Ok(0) => {
+                self.finish();
+                return false;
+            }
+            Ok(_) => {}
+            Err(error) => {
+                let span = self.char_span(0);
+                self.errors.push(Diagnostic::new(
+                    format!("Unreadable source code : {}", error), 
+                    span
+                ));
+                self.finish();
+                return false;
             }
+        }
+
+        self.content_start += self.content.len();
+        std::mem::swap(&mut self.content, &mut self.buffer);
 
-            // SEE This file's documentation
-            if self.about_new_lines(c)
-                || self.about_asm(c)
-                || self.about_strings(c)
-                || self.about_others(c, i)
-            {
+        let mut i = 0;
+        while let Some(c) = self.content[i..].chars().next() {
+            self.pos = self.content_start + i;
+            i += c.len_utf8();
+
+            // The UTF-8 byte order mark is not a part of the source code 
+            if self.pos == 0 && c == '\u{FEFF}' {
+                self.push_trivia(c, false);
                 continue;
             }
+
+            self.about_char(c);
+            self.next_position(c);
+        }
+        self.pos = self.content_start + self.content.len();
+
+        true
+    }
+
+    /// Pushes the last tokens and reports what was never closed
+    fn finish(&mut self) {
+        self.is_finished = true;
+
+        if self.is_string {
+            self.push_unterminated_string();
+        }
+
+        // Only the outer block comment is reported, the nested ones are part
+        // of it
+        if let Some(span) = self.block_comments.first() {
+            self.errors.push(Diagnostic::new("Unclosed block comment".to_string(), *span));
+        }
+        if let Some(span) = self.asm_block {
+            self.errors.push(Diagnostic::new("Unclosed assembly block".to_string(), span));
         }
+        // In the same order as in the source code
+        self.errors.sort_by_key(|error| error.span().start());
 
         // Push the last token
         if self.token != String::new() {
@@ -116,72 +277,183 @@ impl Tokenizer {
 
         // Always put a "NewLine" token at the end if not here
         if self.token != Token::NewLine.to_string() {
-            self.token = Token::NewLine.to_string();
-            self.push_token();
+            let span = self.char_span(0);
+            self.push(Token::NewLine, span);
         }
+    }
 
-        // Because the clone of `self.tokenized` is enumerated, and not the real
-        // `self.tokenized`, when `self.tokenized` is updated, the index of `i`
-        // becomes wrong by `1`. The `j` value permits to shift to the right 
-        // index
-        let mut j = 0;
-        
-        let mut previous_token = &Token::None;
-
-        for (i, token) in self.tokenized.clone().iter().enumerate() {
-            // Because `<=` and `>=` aren't double characters, they are found
-            // separated in the tokenized vector. Here, one of them is found,
-            // the both characters are replaced by the right token           
-            if (previous_token == &Token::MoreThan 
-                || previous_token == &Token::LessThan) 
-                && token == &Token::Assign 
-            {
-                // No needs for retrieving the old value but a warning is thrown
-                // when the value is not retrieved 
-                let _ = std::mem::replace(
-                    &mut self.tokenized[i - j - 1],             
-                    if previous_token == &Token::MoreThan { 
-                        Token::MoreThanOrEqual 
-                    } else { // means == `Token::LessThan`
-                        Token::LessThanOrEqual 
-                    }
-                );
-                   
-                self.tokenized.remove(i - j);
-                j += 1;
-            }
-            
-            previous_token = token;
-        }
+    /// Runs the tokenizer and gives the tokens with the errors found, in the
+    /// same order as in the source code
+    ///
+    /// The tokens are always given, even when errors are found. The wrong parts
+    /// of the source code are `Token::Other` tokens
+    pub fn tokenize(mut self) -> (Vec<SpannedToken>, Vec<Diagnostic>) {
+        self.run();
+        (self.tokenized, self.errors)
     }
 
-    /// Returns an immutable 2D vector of the tokenized source code
-    pub fn tokenized(&self) -> &Vec<Token> {
+    /// Returns an immutable 2D vector of the tokenized source code, each token
+    /// comes with its location
+    pub fn tokenized(&self) -> &Vec<SpannedToken> {
         &self.tokenized
     }
 
-    /// For `Self::from_path()`
-    fn read_file_content(file_path: &Path) -> Result<String, io::Error> {
-        let mut source_code = String::new();
+    /// Returns the errors found while tokenizing, the related tokens are still
+    /// in the tokenized vector as `Token::Other`
+    pub fn errors(&self) -> &Vec<Diagnostic> {
+        &self.errors
+    }
 
-        let mut stream = File::open(file_path)?;
-        stream.read_to_string(&mut source_code)?;
+    /// Names of the identifiers found, `Token::Ident` of the same name share 
+    /// the same `Symbol`
+    pub fn symbols(&self) -> &SymbolTable {
+        &self.symbols
+    }
 
-        Ok(source_code)
+    /// Returns the next token without consuming it, see the `Iterator` 
+    /// implementation
+    pub fn peek(&mut self) -> Option<&SpannedToken> {
+        self.fill_queue();
+        self.queue.front()
     }
 
-    fn about_new_lines(&mut self, c: char) -> bool {
+    /// Tokenizes the next lines until at least one token is waiting in the 
+    /// queue, or until the end of the source code
+    fn fill_queue(&mut self) {
+        while self.queue.is_empty() && self.tokenize_line() {
+            self.queue.extend(self.tokenized.drain(..));
+        }
+        // Tokens pushed when finishing
+        self.queue.extend(self.tokenized.drain(..));
+    }
+
+    /// Source code of the current line, from the current character
+    fn rest(&self) -> &str {
+        &self.content[self.pos - self.content_start..]
+    }
+
+    /// Source code located by the span, it has to be on the current line
+    fn source_text(&self, span: Span) -> &str {
+        &self.content[span.start() - self.content_start..span.end() - self.content_start]
+    }
+
+    /// Moves the location to the character following `c`
+    fn next_position(&mut self, c: char) {
         if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else if c == '\t' {
+            self.column += self.tab_width - (self.column - 1) % self.tab_width;
+        } else {
+            self.column += 1;
+        }
+    }
+
+    /// Location of the current character, with a length of `len` bytes
+    fn char_span(&self, len: usize) -> Span {
+        Span::new(self.pos, self.pos + len, self.line, self.column)
+    }
+
+    /// Character following `c` in the source code
+    fn next_char(&self, c: char) -> Option<char> {
+        self.rest()[c.len_utf8()..].chars().next()
+    }
+
+    /// Whether the character is a new line, "\r\n" is a new line starting at
+    /// "\r"
+    fn is_new_line(&self, c: char) -> bool {
+        c == '\n' || (c == '\r' && self.next_char(c) == Some('\n'))
+    }
+
+    fn about_char(&mut self, c: char) {
+        if self.to_skip > 0 {
+            self.to_skip -= 1;
+            return;
+        }
+
+        // Comments will be everytime skipped
+        if !self.is_new_line(c) && self.is_comment {
+            self.push_trivia(c, true);
+            return;
+        }
+
+        // Strings and assembly code are raw, they cannot contain comments
+        if !self.is_string 
+            && !self.is_asm_code 
+            && self.asm_block.is_none() 
+            && self.about_block_comments(c) 
+        {
+            self.push_trivia(c, true);
+            return;
+        }
+
+        // SEE This file's documentation
+        if !self.about_new_lines(c) 
+            && !self.about_asm(c) 
+            && !self.about_strings(c) 
+            && !self.about_numbers(c) 
+        {
+            self.about_others(c);
+        }
+    }
+
+    /// Everything is skipped in block comments, except new lines to keep 
+    /// pushing `Token::NewLine`
+    fn about_block_comments(&mut self, c: char) -> bool {
+        if self.was_comment_delimiter {
+            self.was_comment_delimiter = false;
+            return true;
+        }
+
+        let next = self.next_char(c);
+
+        if c == '/' && next == Some('*') {
+            self.push_token(); // finish the current token before the comment
+            self.block_comments.push(self.char_span(2));
+
+            self.was_comment_delimiter = true;
+            return true;
+        }
+
+        if self.block_comments.is_empty() {
+            return false;
+        }
+
+        if c == '*' && next == Some('/') {
+            self.block_comments.pop();
+
+            self.was_comment_delimiter = true;
+            return true;
+        }
+
+        !self.is_new_line(c)
+    }
+
+    fn about_new_lines(&mut self, c: char) -> bool {
+        // Already pushed with the "\r"
+        if self.was_carriage_return {
+            self.was_carriage_return = false;
+            return true;
+        }
+
+        if self.is_new_line(c) {
+            // Strings cannot be written on several lines
+            if self.is_string {
+                self.push_unterminated_string();
+            }
+
             self.push_token(); // push the line's last token
 
             // By this way, comments are ignored but `Token::NewLine` is pushed
             // Don't forget it's important to know there is a line here to count
             // lines
-            self.tokenized.push(Token::NewLine);
+            let span = self.char_span(if c == '\r' { 2 } else { 1 });
+            self.push(Token::NewLine, span);
 
             // Resets
             self.is_asm_code = false;
             self.is_comment = false;
+            self.was_carriage_return = c == '\r';
 
             return true;
         }
@@ -190,15 +462,39 @@ impl Tokenizer {
     }
 
     fn about_asm(&mut self, c: char) -> bool {
-        if self.is_asm_code {
-            self.token += &format!("{}", c);
+        if self.asm_block.is_some() && c == '}' {
+            self.push_token(); // the block's last line
+            let span = self.char_span(1);
+            self.push(Token::BracketClose, span);
+            self.asm_block = None;
+
             return true;
         }
 
-        if c == '@' {
-            self.token = "@".to_string();
-            self.push_token();
-            self.is_asm_code = true;
+        if self.is_asm_code || self.asm_block.is_some() {
+            // Indentation of the assembly code
+            if self.token.is_empty() && c.is_whitespace() {
+                self.push_trivia(c, false);
+            } else {
+                self.push_char(c);
+            }
+            return true;
+        }
+
+        if c == '@' && !self.is_string {
+            let span = self.char_span(1);
+            self.push_token(); // finish the current token before the assembly
+            self.push(Token::Assembly, span);
+
+            // "@{" starts a block of several lines
+            if self.version.has_assembly_blocks() && self.next_char(c) == Some('{') {
+                let span = Span::new(self.pos + 1, self.pos + 2, self.line, self.column + 1);
+                self.push(Token::BracketOpen, span);
+                self.asm_block = Some(self.char_span(2));
+                self.to_skip = 1;
+            } else {
+                self.is_asm_code = true;
+            }
 
             return true;
         }
@@ -207,22 +503,31 @@ impl Tokenizer {
     }
 
     fn about_strings(&mut self, c: char) -> bool {
-        if c == Token::StringDot.to_string().chars().next().unwrap() {
+        // `Token::StringDot`, without converting it for each character
+        let is_string_dot = c == '\'';
+
+        if is_string_dot && !self.is_escaped {
             if self.is_string {
                 // end of string
                 self.is_string = false;
 
-                self.tokenized.push(Token::from_string(&format!(
-                    "{}{}{}",
-                    Token::StringDot.to_string(),
-                    self.string_content,
-                    Token::StringDot.to_string()
-                )));
+                let span = self.string_start.to(&self.char_span(1));
+                let token = match Token::unescape(&self.string_content) {
+                    Ok(content) => Token::from_string_content(&content),
+                    Err(message) => {
+                        self.errors.push(Diagnostic::new(message, span));
+                        Token::Other(self.source_text(span).to_string())
+                    }
+                };
+                self.push(token, span);
 
                 // Reset the string for the next
-                self.string_content = String::new();
+                self.string_content.clear();
             } else {
+                // The token written just before is complete
+                self.push_token();
                 self.is_string = true;
+                self.string_start = self.char_span(1);
             }
 
             return true;
@@ -231,6 +536,7 @@ impl Tokenizer {
         if self.is_string {
             // string creation
             self.string_content.push(c);
+            self.is_escaped = c == '\\' && !self.is_escaped;
 
             // Don't care of the other possibilities, we want raw characters in
             // the string
@@ -240,43 +546,224 @@ impl Tokenizer {
         false
     }
 
-    fn about_others(&mut self, c: char, i: usize) -> bool {
-        if !c.is_alphanumeric() && c != '_' {
+    /// The string was not closed before the end of the line, the error is
+    /// located at the opening `Token::StringDot`
+    fn push_unterminated_string(&mut self) {
+        let span = Span::new(
+            self.string_start.start(), 
+            self.pos,
+            self.string_start.line(), 
+            self.string_start.column()
+        );
+
+        self.errors.push(Diagnostic::new("Unterminated string".to_string(), span));
+        self.push(Token::Other(self.source_text(span).to_string()), span);
+
+        self.is_string = false;
+        self.is_escaped = false;
+        self.string_content.clear();
+    }
+
+    /// Points and exponent signs are kept in the current token when it's a
+    /// decimal number, for floating-point numbers like "3.14" or "1e-5"
+    fn about_numbers(&mut self, c: char) -> bool {
+        if !self.token.starts_with(|first: char| first.is_ascii_digit()) 
+            || self.token.starts_with("0x") 
+            || self.token.starts_with("0b") 
+            || self.token.starts_with("0o")
+        {
+            return false;
+        }
+
+        let is_next_digit = self.next_char(c).is_some_and(|next| next.is_ascii_digit());
+
+        // Only decimal digits can be written before the point or the 
+        // exponent, "2byte-1" is a subtraction with a typed number
+        let is_decimal = |digits: &str| {
+            digits.chars().all(|c| c.is_ascii_digit() || matches!(c, '_' | '.'))
+        };
+
+        let is_float_part = is_next_digit && match c {
+            '.' => is_decimal(&self.token) && !self.token.contains('.'),
+            '+' | '-' => self.token
+                .strip_suffix(['e', 'E'])
+                .is_some_and(is_decimal),
+            _ => false,
+        };
+
+        if is_float_part {
+            self.push_char(c);
+        }
+        is_float_part
+    }
+
+    fn about_others(&mut self, c: char) -> bool {
+        // Identifiers and numbers are made of XID characters
+        if !c.is_xid_continue() {
             // should be cut
             self.push_token(); // finish the current token...
 
             // ... to create another one with the character
-            if c != ' ' && !self.was_double_char {
-                if i != self.content.len() - 1 && c == self.content.chars().nth(i + 1).unwrap() {
-                    let double_char_as_token = Token::from_string(&format!("{}{}", c, c));
-                    if double_char_as_token == Token::Comment {
+            if c.is_whitespace() {
+                self.push_trivia(c, false);
+                return true;
+            }
+
+            match self.longest_operator() {
+                Some(operator) => {
+                    let token = Token::from_string(operator);
+                    if token == Token::Comment {
                         self.is_comment = true;
+                        self.about_doc_comments();
+                        self.push_trivia(c, true);
                         return true;
                     }
-                    self.tokenized.push(double_char_as_token);
 
-                    self.was_double_char = true;
-                    return true;
-                }
+                    let span = self.char_span(operator.len());
+                    self.push(token, span);
 
-                self.tokenized.push(Token::from_string(&format!("{}", c)));
+                    self.to_skip = operator.chars().count() - 1;
+                }
+                None => {
+                    let span = self.char_span(c.len_utf8());
+                    self.errors.push(Diagnostic::new(
+                        format!("Invalid character '{}'", c.escape_debug()), 
+                        span
+                    ));
+                    self.push(Token::Other(c.to_string()), span);
+                }
             }
-            self.was_double_char = false;
             return true;
         }
 
-        self.token.push(c); // it's still the same token
+        self.push_char(c); // it's still the same token
         false
     }
 
+    /// Longest operator written from the current character, the other 
+    /// possible operators are only a part of it (like "<" for "<=")
+    fn longest_operator(&self) -> Option<&'static str> {
+        let rest = self.rest();
+
+        self.version
+            .operators()
+            .iter()
+            .copied()
+            .filter(|operator| rest.starts_with(operator))
+            .max_by_key(|operator| operator.len())
+    }
+
+    /// Pushes a new token, taking the trivia found before it in lossless mode
+    fn push(&mut self, token: Token, span: Span) {
+        let mut token = SpannedToken::new(token, span);
+
+        if self.is_lossless {
+            token.set_trivia(std::mem::take(&mut self.trivia));
+            token.set_text(self.source_text(span).to_string());
+        }
+        self.tokenized.push(token);
+    }
+
+    /// Saves the current character as trivia for the next token, in lossless
+    /// mode
+    fn push_trivia(&mut self, c: char, is_comment: bool) {
+        // Already in a token, like the text of a documentation comment
+        let is_in_token = self.tokenized
+            .last()
+            .is_some_and(|token| self.pos < token.span().end());
+
+        if !self.is_lossless || is_in_token {
+            return;
+        }
+
+        match self.trivia.last_mut() {
+            Some(Trivia::Comment(text)) if is_comment => text.push(c),
+            Some(Trivia::Whitespace(text)) if !is_comment => text.push(c),
+            _ if is_comment => self.trivia.push(Trivia::Comment(c.to_string())),
+            _ => self.trivia.push(Trivia::Whitespace(c.to_string())),
+        }
+    }
+
+    /// Documentation comments are kept as tokens, the comment starting at the
+    /// current character is read until the end of the line
+    fn about_doc_comments(&mut self) {
+        let rest = self.rest();
+        let comment = match rest.strip_suffix('\n') {
+            // The "\r" of "\r\n" is a part of the `Token::NewLine`
+            Some(line) => line.strip_suffix('\r').unwrap_or(line),
+            None => rest,
+        };
+        let line_end = self.pos + comment.len();
+
+        // "////" is not a documentation comment
+        let token = if comment.starts_with("///") && !comment.starts_with("////") {
+            Token::DocComment(Token::doc_from_comment(&comment[3..]))
+        } else if let Some(text) = comment.strip_prefix("//!") {
+            Token::ModuleDocComment(Token::doc_from_comment(text))
+        } else {
+            return;
+        };
+
+        let span = Span::new(self.pos, line_end, self.line, self.column);
+        self.push(token, span);
+    }
+
+    /// Adds the character to the current token, its location is saved when it
+    /// is the first character of the token
+    fn push_char(&mut self, c: char) {
+        if self.token.is_empty() {
+            self.token_start = self.char_span(0);
+        }
+        self.token.push(c);
+    }
+
+    /// The current token ends just before the current character
     fn push_token(&mut self) {
         if *self.token == String::new() {
             // useless if void
             return;
         }
 
-        self.tokenized.push(Token::from_string(&self.token.clone()));
-        self.token = String::new(); // reset for the next
+        let span = Span::new(
+            self.token_start.start(),
+            self.pos,
+            self.token_start.line(),
+            self.token_start.column()
+        );
+        // Assembly code is not tokenized
+        let token = if self.is_asm_code || self.asm_block.is_some() {
+            Token::AssemblyCode(self.token.trim_end().to_string())
+        } else if self.token.starts_with(|first: char| first.is_ascii_digit()) {
+            Token::number_from_str(&self.token).unwrap_or_else(|message| {
+                self.errors.push(Diagnostic::new(message, span));
+                Token::Other(self.token.clone())
+            })
+        } else {
+            let token = Token::from_str_interned(&self.token, &mut self.symbols, self.version);
+
+            // Not starting with a XID start character
+            if let Token::Other(ref string) = token {
+                self.errors.push(Diagnostic::new(
+                    format!("Invalid identifier '{}'", string), 
+                    span
+                ));
+            }
+            token
+        };
+
+        self.push(token, span);
+        self.token.clear(); // reset for the next
+    }
+}
+
+impl Iterator for Tokenizer {
+    type Item = SpannedToken;
+
+    /// The source code is tokenized on demand, the given tokens are not kept
+    /// in `tokenized()`
+    fn next(&mut self) -> Option<Self::Item> {
+        self.fill_queue();
+        self.queue.pop_front()
     }
 }
 
@@ -302,3 +789,482 @@ fn from_source_code() {
 
     println!("{:?}", tokenizer);
 }
+
+#[test]
+fn spans() {
+    use crate::lang::elements::type_::Type;
+
+    let source_code = "let é = 'abc'\n  ret é >= 2";
+
+    let mut tokenizer = Tokenizer::from_source_code(source_code);
+    tokenizer.run();
+
+    let spans: Vec<(Token, Span)> = tokenizer.tokenized()
+        .iter()
+        .map(|token| (token.token().clone(), token.span()))
+        .collect();
+
+    assert_eq!(spans, vec![
+        (Token::Variable, Span::new(0, 3, 1, 1)),
+        (Token::Ident("é".into()), Span::new(4, 6, 1, 5)),
+        (Token::Assign, Span::new(7, 8, 1, 7)),
+        (Token::StringLiteral("abc".to_string()), Span::new(9, 14, 1, 9)),
+        (Token::NewLine, Span::new(14, 15, 1, 14)),
+        (Token::Return, Span::new(17, 20, 2, 3)),
+        (Token::Ident("é".into()), Span::new(21, 23, 2, 7)),
+        (Token::MoreThanOrEqual, Span::new(24, 26, 2, 9)),
+        (Token::IntLiteral(2, Type::None), Span::new(27, 28, 2, 12)),
+        (Token::NewLine, Span::new(28, 28, 2, 13)),
+    ]);
+}
+
+#[test]
+fn numbers() {
+    use crate::lang::elements::type_::Type;
+    use crate::lang::tokens::Float;
+
+    let source_code = "2.75 1e-5 2.5E+3 0x1e-5 1_000int 1.foo 0b12 2byte-1 1e999";
+
+    let mut tokenizer = Tokenizer::from_source_code(source_code);
+    tokenizer.run();
+
+    let tokens: Vec<Token> = tokenizer.tokenized()
+        .iter()
+        .map(|token| token.token().clone())
+        .collect();
+
+    assert_eq!(tokens, vec![
+        Token::FloatLiteral(Float::new(2.75)),
+        Token::FloatLiteral(Float::new(1e-5)),
+        Token::FloatLiteral(Float::new(2.5e3)),
+        Token::IntLiteral(0x1e, Type::None),
+        Token::Minus,
+        Token::IntLiteral(5, Type::None),
+        Token::IntLiteral(1000, Type::Integer),
+        Token::IntLiteral(1, Type::None),
+        Token::Point,
+        Token::Ident("foo".into()),
+        Token::Other("0b12".to_string()),
+        Token::IntLiteral(2, Type::Byte),
+        Token::Minus,
+        Token::IntLiteral(1, Type::None),
+        Token::Other("1e999".to_string()),
+        Token::NewLine,
+    ]);
+
+    let errors: Vec<&str> = tokenizer.errors()
+        .iter()
+        .map(|error| &source_code[error.span().range()])
+        .collect();
+
+    assert_eq!(errors, vec!["0b12", "1e999"]);
+}
+
+#[test]
+fn strings() {
+    let source_code = "'I love Junon !\\n' 'it\\'s' '\\q'\nlet a = 'abc\nlet b = '@'";
+
+    let mut tokenizer = Tokenizer::from_source_code(source_code);
+    tokenizer.run();
+
+    let tokens: Vec<Token> = tokenizer.tokenized()
+        .iter()
+        .map(|token| token.token().clone())
+        .collect();
+
+    assert_eq!(tokens, vec![
+        Token::StringLiteral("I love Junon !\n".to_string()),
+        Token::StringLiteral("it's".to_string()),
+        Token::Other("'\\q'".to_string()),
+        Token::NewLine,
+        Token::Variable,
+        Token::Ident("a".into()),
+        Token::Assign,
+        Token::Other("'abc".to_string()),
+        Token::NewLine,
+        Token::Variable,
+        Token::Ident("b".into()),
+        Token::Assign,
+        Token::CharLiteral('@'),
+        Token::NewLine,
+    ]);
+
+    let errors = tokenizer.errors();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].message(), "Unknown escape sequence '\\q'");
+    assert_eq!(errors[1].message(), "Unterminated string");
+    assert_eq!((errors[1].span().line(), errors[1].span().column()), (2, 9));
+
+    // A string written right after an identifier
+    let mut tokenizer = Tokenizer::from_source_code("abc'def'");
+    tokenizer.run();
+
+    let tokens: Vec<(Token, std::ops::Range<usize>)> = tokenizer.tokenized()
+        .iter()
+        .map(|token| (token.token().clone(), token.span().range()))
+        .collect();
+
+    assert_eq!(tokens, vec![
+        (Token::Ident("abc".into()), 0..3),
+        (Token::StringLiteral("def".to_string()), 3..8),
+        (Token::NewLine, 8..8),
+    ]);
+}
+
+#[test]
+fn block_comments() {
+    let source_code = "let a/* one\n /* nested\n */ line */= 1\n/* 'not a string */ ret a";
+
+    let mut tokenizer = Tokenizer::from_source_code(source_code);
+    tokenizer.run();
+
+    let tokens: Vec<Token> = tokenizer.tokenized()
+        .iter()
+        .map(|token| token.token().clone())
+        .collect();
+
+    assert_eq!(tokens, vec![
+        Token::Variable,
+        Token::Ident("a".into()),
+        Token::NewLine,
+        Token::NewLine,
+        Token::Assign,
+        Token::IntLiteral(1, crate::lang::elements::type_::Type::None),
+        Token::NewLine,
+        Token::Return,
+        Token::Ident("a".into()),
+        Token::NewLine,
+    ]);
+    assert_eq!(tokenizer.tokenized()[4].span().line(), 3);
+    assert!(tokenizer.errors().is_empty());
+
+    let mut tokenizer = Tokenizer::from_source_code("ret /* /* */\n5");
+    tokenizer.run();
+
+    let errors = tokenizer.errors();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message(), "Unclosed block comment");
+    assert_eq!(errors[0].span(), Span::new(4, 6, 1, 5));
+}
+
+#[test]
+fn doc_comments() {
+    let source_code = "//! Module\n/// Doc\n//// Normal\n// Normal\nfun";
+
+    let mut tokenizer = Tokenizer::from_source_code(source_code);
+    tokenizer.run();
+
+    let tokens: Vec<Token> = tokenizer.tokenized()
+        .iter()
+        .map(|token| token.token().clone())
+        .collect();
+
+    assert_eq!(tokens, vec![
+        Token::ModuleDocComment("Module".to_string()),
+        Token::NewLine,
+        Token::DocComment("Doc".to_string()),
+        Token::NewLine,
+        Token::NewLine,
+        Token::NewLine,
+        Token::Function,
+        Token::NewLine,
+    ]);
+    assert_eq!(&source_code[tokenizer.tokenized()[2].span().range()], "/// Doc");
+}
+
+#[test]
+fn lossless() {
+    let lossless_source = |source_code: &str| -> String {
+        let mut tokenizer = Tokenizer::from_source_code(source_code);
+        tokenizer.set_lossless(true);
+        tokenizer.run();
+
+        tokenizer.tokenized()
+            .iter()
+            .map(|token| token.lossless_text())
+            .collect()
+    };
+
+    let sources = [
+        "fun main {\n\tlet a = 5;    ret\n}\n // annoying comment",
+        "let a = 0xFF_int /* one\n /* nested\n */ two */+ 'it\\'s'\r\n",
+        "//! Module\n/// Doc\n\t\t//// Normal\nfun foo(a: int) { ret a <= 2 }  ",
+        "if a < = b {\n    @ mov rax, \t1 // asm\n}\n'unterminated\n/* unclosed",
+        "  let é: byte = 2.5e-3 $ 12abc   \n\n",
+        "@{\n\tmov rax, rdi // not a comment \r\n\n\tsyscall\n} in(rdi) a\n@{ nop",
+        "",
+    ];
+    for source_code in sources {
+        assert_eq!(lossless_source(source_code), source_code);
+    }
+
+    for file_path in ["tests/test1.ju", "tests/parser.ju", "tests/operations.ju"] {
+        let source_code = std::fs::read_to_string(file_path).unwrap();
+        assert_eq!(lossless_source(&source_code), source_code);
+    }
+}
+
+#[test]
+fn trivia() {
+    let source_code = "let\ta /* b */ // c\n";
+
+    let mut tokenizer = Tokenizer::from_source_code(source_code);
+    tokenizer.set_lossless(true);
+    tokenizer.run();
+
+    let tokens = tokenizer.tokenized();
+    assert_eq!(tokens[0].text(), "let");
+    assert_eq!(tokens[1].trivia(), &vec![Trivia::Whitespace("\t".to_string())]);
+    assert_eq!(tokens[2].token(), &Token::NewLine);
+    assert_eq!(tokens[2].trivia(), &vec![
+        Trivia::Whitespace(" ".to_string()),
+        Trivia::Comment("/* b */".to_string()),
+        Trivia::Whitespace(" ".to_string()),
+        Trivia::Comment("// c".to_string()),
+    ]);
+}
+
+#[test]
+fn unicode() {
+    let source_code = "\u{FEFF}let café_ß = 'ü' $\r\nret 名前 ²\r\n/// Doc\r\n";
+
+    let mut tokenizer = Tokenizer::from_source_code(source_code);
+    tokenizer.run();
+
+    let tokens: Vec<(Token, Span)> = tokenizer.tokenized()
+        .iter()
+        .map(|token| (token.token().clone(), token.span()))
+        .collect();
+
+    assert_eq!(tokens, vec![
+        (Token::Variable, Span::new(3, 6, 1, 1)),
+        (Token::Ident("café_ß".into()), Span::new(7, 15, 1, 5)),
+        (Token::Assign, Span::new(16, 17, 1, 12)),
+        (Token::CharLiteral('ü'), Span::new(18, 22, 1, 14)),
+        (Token::Other("$".to_string()), Span::new(23, 24, 1, 18)),
+        (Token::NewLine, Span::new(24, 26, 1, 19)),
+        (Token::Return, Span::new(26, 29, 2, 1)),
+        (Token::Ident("名前".into()), Span::new(30, 36, 2, 5)),
+        (Token::Other("²".to_string()), Span::new(37, 39, 2, 8)),
+        (Token::NewLine, Span::new(39, 41, 2, 9)),
+        (Token::DocComment("Doc".to_string()), Span::new(41, 48, 3, 1)),
+        (Token::NewLine, Span::new(48, 50, 3, 8)),
+        (Token::NewLine, Span::new(50, 50, 4, 1)),
+    ]);
+
+    let errors: Vec<String> = tokenizer.errors()
+        .iter()
+        .map(|error| error.to_string())
+        .collect();
+
+    assert_eq!(errors, vec![
+        "1:18: Invalid character '$'".to_string(),
+        "2:8: Invalid character '²'".to_string(),
+    ]);
+
+    let mut tokenizer = Tokenizer::from_source_code(source_code);
+    tokenizer.set_lossless(true);
+    tokenizer.run();
+
+    let lossless_source: String = tokenizer.tokenized()
+        .iter()
+        .map(|token| token.lossless_text())
+        .collect();
+    assert_eq!(lossless_source, source_code);
+}
+
+#[test]
+fn operators() {
+    let source_code = "a<=b>=c a < = b != !d && e||f -> g::h += -= *= /= << >> == = /";
+
+    let mut tokenizer = Tokenizer::from_source_code(source_code);
+    tokenizer.run();
+
+    let tokens: Vec<Token> = tokenizer.tokenized()
+        .iter()
+        .filter(|token| !matches!(token.token(), Token::Ident(_)))
+        .map(|token| token.token().clone())
+        .collect();
+
+    assert_eq!(tokens, vec![
+        Token::LessThanOrEqual,
+        Token::MoreThanOrEqual,
+        Token::LessThan,
+        Token::Assign,
+        Token::NotEqual,
+        Token::Not,
+        Token::And,
+        Token::Or,
+        Token::Arrow,
+        Token::DoubleColon,
+        Token::PlusAssign,
+        Token::MinusAssign,
+        Token::MultiplyAssign,
+        Token::DivideAssign,
+        Token::ShiftLeft,
+        Token::ShiftRight,
+        Token::Equal,
+        Token::Assign,
+        Token::Divide,
+        Token::NewLine,
+    ]);
+    assert_eq!(tokenizer.tokenized()[1].span(), Span::new(1, 3, 1, 2));
+    assert!(tokenizer.errors().is_empty());
+}
+
+#[test]
+fn diagnostics() {
+    let source_code = "/* unclosed\nlet a = 0b2 # 'ok'\nlet b = 'end\n";
+
+    let (tokens, errors) = Tokenizer::from_source_code(source_code).tokenize();
+
+    assert_eq!(tokens.last().map(|token| token.token()), Some(&Token::NewLine));
+
+    let errors: Vec<String> = errors
+        .iter()
+        .map(|error| error.to_string())
+        .collect();
+
+    assert_eq!(errors, vec![
+        "1:1: Unclosed block comment".to_string(),
+    ]);
+
+    let source_code = "let a = 0b2 # 'ok'\nlet b = 'end\n";
+
+    let (_, errors) = Tokenizer::from_source_code(source_code).tokenize();
+
+    let errors: Vec<(String, &str)> = errors
+        .iter()
+        .map(|error| (error.to_string(), &source_code[error.span().range()]))
+        .collect();
+
+    assert_eq!(errors, vec![
+        ("1:9: Invalid digit '2' in number '0b2'".to_string(), "0b2"),
+        ("1:13: Invalid character '#'".to_string(), "#"),
+        ("2:9: Unterminated string".to_string(), "'end"),
+    ]);
+}
+
+#[test]
+fn assembly() {
+    let source_code = "@ mov rax, 1\n@{\n    mov rax, rdi  \n\n    syscall\n} out(rax) a";
+
+    let mut tokenizer = Tokenizer::from_source_code(source_code);
+    tokenizer.run();
+
+    let tokens: Vec<Token> = tokenizer.tokenized()
+        .iter()
+        .map(|token| token.token().clone())
+        .collect();
+
+    assert_eq!(tokens, vec![
+        Token::Assembly,
+        Token::AssemblyCode("mov rax, 1".to_string()),
+        Token::NewLine,
+        Token::Assembly,
+        Token::BracketOpen,
+        Token::NewLine,
+        Token::AssemblyCode("mov rax, rdi".to_string()),
+        Token::NewLine,
+        Token::NewLine,
+        Token::AssemblyCode("syscall".to_string()),
+        Token::NewLine,
+        Token::BracketClose,
+        Token::Ident("out".into()),
+        Token::ParenOpen,
+        Token::Ident("rax".into()),
+        Token::ParenClose,
+        Token::Ident("a".into()),
+        Token::NewLine,
+    ]);
+    assert_eq!(tokenizer.tokenized()[6].span().line(), 3);
+    assert_eq!(tokenizer.tokenized()[6].span().column(), 5);
+    assert!(tokenizer.errors().is_empty());
+
+    let mut tokenizer = Tokenizer::from_source_code("let a\n@{\n    nop\n");
+    tokenizer.run();
+
+    let errors = tokenizer.errors();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message(), "Unclosed assembly block");
+    assert_eq!(errors[0].span(), Span::new(6, 8, 2, 1));
+}
+
+#[test]
+fn iterator() {
+    /// Gives the source code byte by byte, like a slow stream
+    struct ByteReader(Vec<u8>, usize);
+
+    impl Read for ByteReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.1 >= self.0.len() || buf.is_empty() {
+                return Ok(0);
+            }
+            buf[0] = self.0[self.1];
+            self.1 += 1;
+            Ok(1)
+        }
+    }
+
+    let source_code = std::fs::read_to_string("tests/test1.ju").unwrap() 
+        + "/* unclosed\n";
+
+    let mut tokenizer = Tokenizer::from_source_code(&source_code);
+    tokenizer.set_lossless(true);
+    tokenizer.run();
+
+    let mut stream = Tokenizer::from_reader(ByteReader(source_code.as_bytes().to_vec(), 0));
+    stream.set_lossless(true);
+
+    assert_eq!(stream.peek(), tokenizer.tokenized().first());
+    assert_eq!(stream.peek(), tokenizer.tokenized().first());
+
+    let streamed: Vec<SpannedToken> = stream.by_ref().collect();
+    assert_eq!(&streamed, tokenizer.tokenized());
+    assert_eq!(stream.errors(), tokenizer.errors());
+    assert!(stream.tokenized().is_empty());
+    assert_eq!(stream.next(), None);
+
+    let mut stream = Tokenizer::from_reader(&b"let a\nret \xFF"[..]);
+
+    let tokens: Vec<Token> = stream.by_ref().map(|token| token.token().clone()).collect();
+    assert_eq!(tokens, vec![
+        Token::Variable,
+        Token::Ident("a".into()),
+        Token::NewLine,
+        Token::NewLine,
+    ]);
+    assert_eq!(stream.errors().len(), 1);
+    assert!(stream.errors()[0].message().starts_with("Unreadable source code"));
+}
+
+#[test]
+fn symbols() {
+    let mut tokenizer = Tokenizer::from_source_code("let a = a + b\nfoo(a, b)");
+    tokenizer.run();
+
+    assert_eq!(tokenizer.symbols().len(), 3);
+    assert_eq!(tokenizer.tokenized()[1], Token::Ident("a".into()));
+    assert_eq!(tokenizer.tokenized()[1].token(), tokenizer.tokenized()[3].token());
+}
+
+#[test]
+fn tab_width() {
+    let source_code = "\tlet a\n  \tret\ta\tb";
+
+    let columns = |tab_width: usize| -> Vec<usize> {
+        let mut tokenizer = Tokenizer::from_source_code(source_code);
+        tokenizer.set_tab_width(tab_width);
+        tokenizer.run();
+
+        tokenizer.tokenized()
+            .iter()
+            .filter(|token| **token != Token::NewLine)
+            .map(|token| token.span().column())
+            .collect()
+    };
+
+    assert_eq!(columns(1), vec![2, 6, 4, 8, 10]);
+    assert_eq!(columns(4), vec![5, 9, 5, 9, 13]);
+    assert_eq!(columns(8), vec![9, 13, 9, 17, 25]);
+}